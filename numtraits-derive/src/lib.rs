@@ -0,0 +1,258 @@
+//! `#[derive(UpCastAs)]` for newtypes over the primitives of `numtraits`. Use it through the
+//! `derive` feature of `numtraits`, which re-exports it.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+
+/// Gives a newtype the place of the type it wraps in the hierarchy: a `struct Meters(f64)` can be
+/// built from everything a `f64` can, so `Meters: UpCastAs<f64>`.
+///
+/// ```
+/// # use numtraits::*;
+/// #[derive(UpCastAs)]
+/// struct Meters(f64);
+///
+/// fn example<T: UpCastAs<f64>>() -> T {
+///     cast(3u8)
+/// }
+///
+/// let Meters(m) = example();
+/// assert_eq!(m, 3.0);
+/// ```
+///
+/// The sources can be narrowed down with an `up_cast` attribute. `max = T` only keeps those
+/// below `T`, which must itself be below the inner type, and `only(A, B, ...)` keeps exactly the
+/// listed ones:
+///
+/// ```
+/// # use numtraits::*;
+/// #[derive(UpCastAs)]
+/// #[up_cast(max = u16)]
+/// struct UserId(u32);
+///
+/// let UserId(id) = cast(7u8);
+/// assert_eq!(id, 7);
+/// ```
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// #[derive(UpCastAs)]
+/// #[up_cast(max = u16)]
+/// struct UserId(u32);
+///
+/// let _: UserId = cast(7u32); // Error, u32 is above the max
+/// ```
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// #[derive(UpCastAs)]
+/// #[up_cast(only(u8, i8))]
+/// struct Ratio(f32);
+///
+/// let _: Ratio = cast(7u16); // Error, u16 is not listed
+/// ```
+#[proc_macro_derive(UpCastAs, attributes(up_cast))]
+pub fn derive_up_cast_as(input: TokenStream) -> TokenStream {
+    let out = match expand(input) {
+        Ok(out) => out,
+        Err(msg) => format!("compile_error!({:?});", msg),
+    };
+    out.parse().unwrap()
+}
+
+/// Which sources the newtype accepts.
+enum Sources {
+    /// Everything below the inner type.
+    All,
+    /// Everything below the given type.
+    Max(String),
+    /// Exactly the given types.
+    Only(Vec<String>),
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let mut tokens = input.into_iter().peekable();
+    let mut sources = Sources::All;
+
+    // Attributes and visibility, up to the `struct` keyword.
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(attr)) = tokens.next() {
+                    if let Some(s) = parse_attr(&attr)? {
+                        sources = s;
+                    }
+                }
+            }
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "struct" => break,
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "pub" => {
+                if let Some(TokenTree::Group(g)) = tokens.peek() {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        tokens.next();
+                    }
+                }
+            }
+            _ => return Err("`UpCastAs` can only be derived for structs".into()),
+        }
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(i)) => i.to_string(),
+        _ => return Err("expected the name of the struct".into()),
+    };
+    let body = match tokens.next() {
+        Some(TokenTree::Group(g)) => g,
+        Some(TokenTree::Punct(ref p)) if p.as_char() == '<' => {
+            return Err("`UpCastAs` cannot be derived for generic structs".into())
+        }
+        _ => return Err("`UpCastAs` can only be derived for structs with one field".into()),
+    };
+    let fields = split_commas(body.stream());
+    if fields.len() != 1 {
+        return Err("`UpCastAs` can only be derived for structs with one field".into());
+    }
+    let (field, inner) = match body.delimiter() {
+        Delimiter::Parenthesis => field_type(&fields[0], false)?,
+        Delimiter::Brace => field_type(&fields[0], true)?,
+        _ => return Err("`UpCastAs` can only be derived for structs with one field".into()),
+    };
+    let wrap = |v: &str| match field {
+        Some(ref f) => format!("{} {{ {}: {} }}", name, f, v),
+        None => format!("{}({})", name, v),
+    };
+
+    let edge = "::numtraits::UpCastFrom";
+    Ok(match sources {
+        Sources::All => format!(
+            "impl<__V> {edge}<__V> for {name} where {inner}: {edge}<__V> {{
+                #[inline(always)]
+                fn from(v: __V) -> Self {{ {body} }}
+            }}",
+            edge = edge,
+            name = name,
+            inner = inner,
+            body = wrap(&format!("<{} as {}<__V>>::from(v)", inner, edge)),
+        ),
+        Sources::Max(max) => format!(
+            "const _: () = {{
+                fn max_is_below_inner<T: ::numtraits::UpCastAs<{max}>>() {{}}
+                let _ = max_is_below_inner::<{inner}>;
+            }};
+            impl<__V> {edge}<__V> for {name} where {max}: {edge}<__V>, {inner}: {edge}<__V> {{
+                #[inline(always)]
+                fn from(v: __V) -> Self {{ {body} }}
+            }}",
+            edge = edge,
+            name = name,
+            inner = inner,
+            max = max,
+            body = wrap(&format!("<{} as {}<__V>>::from(v)", inner, edge)),
+        ),
+        Sources::Only(list) => list
+            .iter()
+            .map(|src| {
+                format!(
+                    "impl {edge}<{src}> for {name} {{
+                        #[inline(always)]
+                        fn from(v: {src}) -> Self {{ {body} }}
+                    }}",
+                    edge = edge,
+                    name = name,
+                    src = src,
+                    body = wrap(&format!("<{} as {}<{}>>::from(v)", inner, edge, src)),
+                )
+            })
+            .collect(),
+    })
+}
+
+/// Reads `up_cast(max = T)` or `up_cast(only(A, B))` out of the brackets of an attribute, or
+/// `None` for any other attribute.
+fn parse_attr(attr: &Group) -> Result<Option<Sources>, String> {
+    let mut tokens = attr.stream().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ref i)) if i.to_string() == "up_cast" => {}
+        _ => return Ok(None),
+    }
+    let args = match tokens.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => g.stream(),
+        _ => return Err("expected `up_cast(max = T)` or `up_cast(only(A, B, ...))`".into()),
+    };
+    let mut args = args.into_iter();
+    match (args.next(), args.next()) {
+        (Some(TokenTree::Ident(ref i)), Some(TokenTree::Punct(ref p)))
+            if i.to_string() == "max" && p.as_char() == '=' =>
+        {
+            let max: TokenStream = args.collect();
+            if max.is_empty() {
+                return Err("expected a type after `max =`".into());
+            }
+            Ok(Some(Sources::Max(max.to_string())))
+        }
+        (Some(TokenTree::Ident(ref i)), Some(TokenTree::Group(ref g)))
+            if i.to_string() == "only" && g.delimiter() == Delimiter::Parenthesis =>
+        {
+            let list = split_commas(g.stream()).iter().map(|t| t.to_string()).collect();
+            Ok(Some(Sources::Only(list)))
+        }
+        _ => Err("expected `up_cast(max = T)` or `up_cast(only(A, B, ...))`".into()),
+    }
+}
+
+/// The type of a field, and its name when `named`, skipping attributes and visibility.
+fn field_type(field: &TokenStream, named: bool) -> Result<(Option<String>, String), String> {
+    let mut tokens = field.clone().into_iter().peekable();
+    loop {
+        match tokens.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                tokens.next();
+                tokens.next();
+            }
+            Some(TokenTree::Ident(i)) if i.to_string() == "pub" => {
+                tokens.next();
+                if let Some(TokenTree::Group(g)) = tokens.peek() {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        tokens.next();
+                    }
+                }
+            }
+            _ => break,
+        }
+    }
+    let name = if named {
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Ident(i)), Some(TokenTree::Punct(ref p))) if p.as_char() == ':' => {
+                Some(i.to_string())
+            }
+            _ => return Err("expected a field name".into()),
+        }
+    } else {
+        None
+    };
+    let ty: TokenStream = tokens.collect();
+    Ok((name, ty.to_string()))
+}
+
+/// Splits on the commas which are not inside angle brackets, dropping a trailing one.
+fn split_commas(stream: TokenStream) -> Vec<TokenStream> {
+    let mut parts = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0usize;
+    for tt in stream {
+        match tt {
+            TokenTree::Punct(ref p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(ref p) if p.as_char() == '>' => depth = depth.saturating_sub(1),
+            TokenTree::Punct(ref p) if p.as_char() == ',' && depth == 0 => {
+                parts.push(current.drain(..).collect());
+                continue;
+            }
+            _ => {}
+        }
+        current.push(tt);
+    }
+    if !current.is_empty() {
+        parts.push(current.into_iter().collect());
+    }
+    parts
+}
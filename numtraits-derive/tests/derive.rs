@@ -0,0 +1,41 @@
+use numtraits::*;
+
+#[derive(UpCastAs, Debug, PartialEq)]
+struct Meters(pub f64);
+
+#[derive(UpCastAs, Debug, PartialEq)]
+#[up_cast(max = u16)]
+pub(crate) struct UserId(u32);
+
+#[derive(UpCastAs, Debug, PartialEq)]
+#[up_cast(only(u8, i8))]
+struct Ratio {
+    value: f32,
+}
+
+#[derive(UpCastAs, Debug, PartialEq)]
+struct Count(core::primitive::usize);
+
+fn from_u32<T: UpCastAs<u32>>(x: u32) -> T {
+    cast(x)
+}
+
+fn from_u16<T: UpCastAs<u16>>(x: u16) -> T {
+    cast(x)
+}
+
+#[test]
+fn whole_position() {
+    assert_eq!(from_u32::<Meters>(7), Meters(7.0));
+    assert_eq!(<Meters as UpCastFrom<i64>>::from(-2), Meters(-2.0));
+    assert_eq!(cast::<f32, Meters>(0.5), Meters(0.5));
+    assert_eq!(from_u16::<Count>(9), Count(9));
+}
+
+#[test]
+fn restricted() {
+    assert_eq!(from_u16::<UserId>(65535), UserId(65535));
+    assert_eq!(cast::<u8, UserId>(0), UserId(0));
+    assert_eq!(cast::<u8, Ratio>(3), Ratio { value: 3.0 });
+    assert_eq!(cast::<i8, Ratio>(-3), Ratio { value: -3.0 });
+}
@@ -0,0 +1,282 @@
+use core::marker::PhantomData;
+
+use crate::UpCastFrom;
+
+/// Up casts `v` into `B`, and from there into `T` with std's `From`. This reaches the types which
+/// only know about std, like a big integer with a `From<u64>`.
+///
+/// ```
+/// # use numtraits::*;
+/// struct Big(u64);
+///
+/// impl From<u64> for Big {
+///     fn from(x: u64) -> Big { Big(x) }
+/// }
+///
+/// let Big(x) = cast_via_std::<u16, u64, Big>(7);
+/// assert_eq!(x, 7);
+/// ```
+#[inline(always)]
+pub fn cast_via_std<V, B: UpCastFrom<V>, T: From<B>>(v: V) -> T {
+    T::from(B::from(v))
+}
+
+/// Puts a type with a std `From<B>` in the hierarchy right above `B`, so it can be passed where a
+/// `UpCastAs<B>` is expected. Every cast into it goes through `cast_via_std`.
+///
+/// ```
+/// # use numtraits::*;
+/// struct Big(u64);
+///
+/// impl From<u32> for Big {
+///     fn from(x: u32) -> Big { Big(x as u64) }
+/// }
+///
+/// fn example<T: UpCastAs<u32>>() -> T {
+///     cast(3u8)
+/// }
+///
+/// let Big(x) = example::<ViaStd<Big, u32>>().into_inner();
+/// assert_eq!(x, 3);
+/// ```
+pub struct ViaStd<T, B>(T, PhantomData<fn() -> B>);
+
+impl<T, B> ViaStd<T, B> {
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<V, B: UpCastFrom<V>, T: From<B>> UpCastFrom<V> for ViaStd<T, B> {
+    #[inline(always)]
+    fn from(v: V) -> ViaStd<T, B> {
+        ViaStd(cast_via_std::<V, B, T>(v), PhantomData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cast, try_cast, Lattice, Range};
+    use core::any::type_name;
+    use core::num::{NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize};
+    use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+    use std::vec::Vec;
+
+    #[cfg(feature = "half")]
+    use crate::{BF16, F16};
+
+    type Edges = Vec<(&'static str, &'static str)>;
+
+    // Every edge which std also has a `From` for. Both are exact there, so they have to agree.
+    // Gives back the edges it checked.
+    macro_rules! agree {
+        ($($b:ident => $($a:ident),+;)*) => ({
+            agree!(@check [$($b => $($a, [<$a>::MIN, <$a>::MAX, 0 as $a, 1 as $a]),+;)*])
+        });
+        (nonzero $($b:ident => $($a:ident),+;)*) => ({
+            agree!(@check [$($b => $($a, [<$a>::MIN, <$a>::MAX, <$a>::new(1).unwrap()]),+;)*])
+        });
+        (@check [$($b:ident => $($a:ident, $samples:expr),+;)*]) => ({
+            let mut edges = Edges::new();
+            $($(
+                for x in $samples {
+                    assert_eq!(cast::<$a, $b>(x), <$b as From<$a>>::from(x));
+                }
+                edges.push((type_name::<$a>(), type_name::<$b>()));
+            )+)*
+            edges
+        })
+    }
+
+    // The reverse of the integer edges, against std's `TryFrom`.
+    macro_rules! agree_try {
+        ($($b:ident => $($a:ident),+;)*) => ($($(
+            let samples = [
+                <$b>::MIN, <$b>::MAX, 0, <$a>::MIN as $b, <$a>::MAX as $b,
+                (<$a>::MIN as $b).wrapping_sub(1), (<$a>::MAX as $b).wrapping_add(1),
+            ];
+            for y in samples {
+                assert_eq!(try_cast::<$b, $a>(y).ok(), <$a as TryFrom<$b>>::try_from(y).ok());
+            }
+        )+)*)
+    }
+
+    // Edges which only have to exist.
+    macro_rules! edges {
+        ($($b:ident => $($a:ident),+;)*) => ({
+            let mut edges = Edges::new();
+            $($(
+                edge::<$a, $b>();
+                edges.push((type_name::<$a>(), type_name::<$b>()));
+            )+)*
+            edges
+        })
+    }
+
+    // Every edge of the range table, read off the `Lattice<Range>` slots of the types above.
+    macro_rules! table {
+        ($($t:ident),*) => ({
+            let mut edges = Edges::new();
+            $(table!(@slots edges $t [
+                L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+                L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+            ]);)*
+            edges
+        });
+        (@slots $edges:ident $t:ident [$($slot:ident)*]) => ($(
+            let a = type_name::<<$t as Lattice<Range>>::$slot>();
+            if a != type_name::<$t>() && !$edges.contains(&(a, type_name::<$t>())) {
+                $edges.push((a, type_name::<$t>()));
+            }
+        )*)
+    }
+
+    fn edge<V, T: UpCastFrom<V>>() {}
+
+    fn with_from() -> Edges {
+        let mut edges = agree! {
+            u16 => u8;
+            u32 => u16, u8;
+            u64 => u32, u16, u8;
+            u128 => u64, u32, u16, u8;
+            usize => u16, u8;
+            i16 => i8, u8;
+            i32 => i16, i8, u16, u8;
+            i64 => i32, i16, i8, u32, u16, u8;
+            i128 => i64, i32, i16, i8, u64, u32, u16, u8;
+            isize => i16, i8, u8;
+            f32 => u16, u8, i16, i8;
+            f64 => f32, u32, u16, u8, i32, i16, i8;
+        };
+        edges.extend(agree! {
+            nonzero
+            NonZeroU16 => NonZeroU8;
+            NonZeroU32 => NonZeroU16, NonZeroU8;
+            NonZeroU64 => NonZeroU32, NonZeroU16, NonZeroU8;
+            NonZeroU128 => NonZeroU64, NonZeroU32, NonZeroU16, NonZeroU8;
+            NonZeroUsize => NonZeroU16, NonZeroU8;
+            NonZeroI16 => NonZeroI8, NonZeroU8;
+            NonZeroI32 => NonZeroI16, NonZeroI8, NonZeroU16, NonZeroU8;
+            NonZeroI64 => NonZeroI32, NonZeroI16, NonZeroI8, NonZeroU32, NonZeroU16, NonZeroU8;
+            NonZeroI128 => NonZeroI64, NonZeroI32, NonZeroI16, NonZeroI8,
+                           NonZeroU64, NonZeroU32, NonZeroU16, NonZeroU8;
+            NonZeroIsize => NonZeroI16, NonZeroI8, NonZeroU8;
+        });
+        edges
+    }
+
+    // The edges std has no `From` for, on purpose. std only converts when the value is exact on
+    // every target, while `UpCastAs` is about the range.
+    fn without_from() -> Edges {
+        // Integers into floats which may round: `16777217i64` becomes `16777216f32`.
+        let mut edges = edges! {
+            f32 => u32, u64, i32, i64, i128, usize, isize;
+            f64 => u64, u128, i64, i128, usize, isize;
+        };
+        // The pointer sized integers, which std keeps portable to 16 bit targets.
+        edges.extend(edges! {
+            u64 => usize;
+            u128 => usize;
+            i128 => usize, isize;
+            i64 => isize;
+            NonZeroU64 => NonZeroUsize;
+            NonZeroU128 => NonZeroUsize;
+            NonZeroI128 => NonZeroUsize, NonZeroIsize;
+            NonZeroI64 => NonZeroIsize;
+        });
+        #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+        edges.extend(edges! {
+            usize => u32;
+            isize => i32, u16;
+            NonZeroUsize => NonZeroU32;
+            NonZeroIsize => NonZeroI32, NonZeroU16;
+        });
+        #[cfg(target_pointer_width = "64")]
+        edges.extend(edges! {
+            usize => u64;
+            isize => i64, u32;
+            NonZeroUsize => NonZeroU64;
+            NonZeroIsize => NonZeroI64, NonZeroU32;
+        });
+        #[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+        edges.extend(edges! {
+            u32 => usize;
+            i64 => usize;
+            NonZeroU32 => NonZeroUsize;
+            NonZeroI64 => NonZeroUsize;
+        });
+        // The half precision floats are not std types at all.
+        #[cfg(feature = "half")]
+        edges.extend(edges! {
+            F16 => u8, i8;
+            BF16 => F16, u8, i8;
+            f32 => F16, BF16;
+            f64 => F16, BF16;
+        });
+        edges
+    }
+
+    #[test]
+    fn agrees_with_from() {
+        with_from();
+    }
+
+    #[test]
+    fn agrees_with_try_from() {
+        agree_try! {
+            u16 => u8;
+            u32 => u16, u8;
+            u64 => u32, u16, u8, usize;
+            u128 => u64, u32, u16, u8, usize;
+            usize => u16, u8;
+            i16 => i8, u8;
+            i32 => i16, i8, u16, u8;
+            i64 => i32, i16, i8, u32, u16, u8, isize;
+            i128 => i64, i32, i16, i8, u64, u32, u16, u8, isize, usize;
+            isize => i16, i8, u8;
+        }
+    }
+
+    #[test]
+    fn differs_from_std() {
+        without_from();
+        assert_eq!(cast::<i64, f32>(16777217), 16777216.0);
+    }
+
+    // The lists above are written out by hand, so check they cover every edge of the table.
+    #[test]
+    fn covers_the_table() {
+        let mut listed = with_from();
+        listed.extend(without_from());
+        #[allow(unused_mut)]
+        let mut table = table!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize,
+                               f32, f64, NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64,
+                               NonZeroU128, NonZeroUsize, NonZeroI8, NonZeroI16, NonZeroI32,
+                               NonZeroI64, NonZeroI128, NonZeroIsize);
+        #[cfg(feature = "half")]
+        table.extend(table!(F16, BF16));
+        for (a, b) in table {
+            assert!(listed.contains(&(a, b)), "{} => {} is not checked against std", b, a);
+        }
+    }
+
+    #[test]
+    fn via_std() {
+        #[derive(Debug, PartialEq)]
+        struct Big(u128);
+
+        impl From<u64> for Big {
+            fn from(x: u64) -> Big { Big(x as u128) }
+        }
+
+        fn sum<T: crate::UpCastAs<u64>>(xs: &[u8]) -> [T; 2] {
+            [cast(xs[0]), cast(xs[1] as u32)]
+        }
+
+        let [a, b] = sum::<ViaStd<Big, u64>>(&[1, 2]);
+        assert_eq!((a.into_inner(), b.into_inner()), (Big(1), Big(2)));
+        assert_eq!(cast_via_std::<i8, i64, i128>(-1), -1);
+    }
+}
@@ -0,0 +1,118 @@
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Any primitive integer of the hierarchy. It is sealed, so generic code may rely on every
+/// implementor being one of them.
+pub trait Integer: sealed::Sealed + Copy {
+    /// The width in bits.
+    const BITS: u32;
+    const MIN: Self;
+    const MAX: Self;
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+/// `u8` through `u128`, and `usize`.
+pub trait Unsigned: Integer {}
+
+/// `i8` through `i128`, and `isize`.
+pub trait Signed: Integer {}
+
+/// Any primitive number of the hierarchy, the types `cast_const!` can convert with `as`.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a primitive number",
+    label = "`cast_const!` only converts primitives",
+    note = "use `cast` for the other types of the hierarchy"
+)]
+pub trait Primitive: sealed::Sealed {}
+
+impl<T: sealed::Sealed> Primitive for T {}
+
+/// `f32` and `f64`.
+pub trait Float: sealed::Sealed + Copy {
+    /// The width in bits.
+    const BITS: u32;
+    /// The most negative finite value.
+    const MIN: Self;
+    /// The largest finite value.
+    const MAX: Self;
+    const ZERO: Self;
+    const ONE: Self;
+    /// The number of significant binary digits, including the implicit one.
+    const MANTISSA_DIGITS: u32;
+    /// `2^MANTISSA_DIGITS`. Every integer from `-MAX_EXACT_INT` to `MAX_EXACT_INT` is exact, the
+    /// next one up is not.
+    const MAX_EXACT_INT: Self;
+}
+
+macro_rules! integer {
+    ($kind:ident: $($t:ident)*) => ($(
+        impl sealed::Sealed for $t {}
+        impl Integer for $t {
+            const BITS: u32 = <$t>::BITS;
+            const MIN: $t = <$t>::MIN;
+            const MAX: $t = <$t>::MAX;
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+        }
+        impl $kind for $t {}
+    )*)
+}
+
+integer!(Unsigned: u8 u16 u32 u64 u128 usize);
+integer!(Signed: i8 i16 i32 i64 i128 isize);
+
+macro_rules! float {
+    ($($t:ident $bits:expr;)*) => ($(
+        impl sealed::Sealed for $t {}
+        impl Float for $t {
+            const BITS: u32 = $bits;
+            const MIN: $t = <$t>::MIN;
+            const MAX: $t = <$t>::MAX;
+            const ZERO: $t = 0.0;
+            const ONE: $t = 1.0;
+            const MANTISSA_DIGITS: u32 = <$t>::MANTISSA_DIGITS;
+            const MAX_EXACT_INT: $t = (1u64 << <$t>::MANTISSA_DIGITS) as $t;
+        }
+    )*)
+}
+
+float! {
+    f32 32;
+    f64 64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range<T: Integer>() -> (T, T) {
+        (T::MIN, T::MAX)
+    }
+
+    fn zero_if_unsigned<T: Unsigned>() -> T {
+        T::MIN
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(range::<i8>(), (-128, 127));
+        assert_eq!(range::<u128>(), (0, u128::MAX));
+        assert_eq!(zero_if_unsigned::<usize>(), 0);
+        assert_eq!(<u16 as Integer>::BITS, 16);
+        assert_eq!(<isize as Integer>::BITS, usize::BITS);
+        assert_eq!((<i64 as Integer>::ZERO, <i64 as Integer>::ONE), (0, 1));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(<f32 as Float>::BITS, 32);
+        assert_eq!(<f32 as Float>::MAX_EXACT_INT, 16777216.0);
+        assert_eq!(<f64 as Float>::MAX_EXACT_INT, 9007199254740992.0);
+        assert_eq!(<f32 as Float>::MAX_EXACT_INT + 1.0, 16777216.0);
+        assert_eq!(<f64 as Float>::MANTISSA_DIGITS, 53);
+        assert_eq!(<f64 as Float>::MIN, -f64::MAX);
+    }
+}
@@ -0,0 +1,157 @@
+use core::cmp::Ordering;
+
+/// Which way a conversion into a float goes when the exact value falls between two floats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The result is at least the exact value.
+    Up,
+    /// The result is at most the exact value.
+    Down,
+    /// The result is no further from zero than the exact value.
+    TowardZero,
+}
+
+trait Step: Copy {
+    /// The next float towards positive infinity.
+    fn step_up(self) -> Self;
+    /// The next float towards negative infinity.
+    fn step_down(self) -> Self;
+    fn is_negative(self) -> bool;
+}
+
+macro_rules! step {
+    ($f:ident, $min_positive:expr) => (
+        impl Step for $f {
+            fn step_up(self) -> $f {
+                if self.is_nan() || self == <$f>::INFINITY {
+                    self
+                } else if self == 0.0 {
+                    $min_positive
+                } else if self > 0.0 {
+                    <$f>::from_bits(self.to_bits() + 1)
+                } else {
+                    <$f>::from_bits(self.to_bits() - 1)
+                }
+            }
+
+            fn step_down(self) -> $f {
+                -(-self).step_up()
+            }
+
+            fn is_negative(self) -> bool {
+                self.is_sign_negative()
+            }
+        }
+    )
+}
+
+step!(f32, <f32>::from_bits(1));
+step!(f64, <f64>::from_bits(1));
+
+/// Moves `r`, the nearest float to some exact value, one step in `dir` when `ord` (`r` compared
+/// to the exact value) says it is on the wrong side.
+fn directed<F: Step>(r: F, ord: Ordering, dir: Direction) -> F {
+    let dir = match dir {
+        Direction::TowardZero if r.is_negative() => Direction::Up,
+        Direction::TowardZero => Direction::Down,
+        dir => dir,
+    };
+    match (ord, dir) {
+        (Ordering::Greater, Direction::Down) => r.step_down(),
+        (Ordering::Less, Direction::Up) => r.step_up(),
+        _ => r,
+    }
+}
+
+/// `Self` is a float which can be built from `V` rounding in a chosen `Direction`.
+pub trait ToFloatRounded<V>: Sized {
+    fn from_rounded(v: V, dir: Direction) -> Self;
+}
+
+macro_rules! directed_rule {
+    ($f:ident => $($i:ident)*) => ($(
+        impl ToFloatRounded<$i> for $f {
+            #[inline]
+            fn from_rounded(v: $i, dir: Direction) -> $f {
+                // `as` rounds to nearest, so `r` is an integer and exact in `$i` unless it went
+                // past `$i::MAX`, which is then the only way it can be wrong.
+                let r = v as $f;
+                let ord = if r >= (<$i>::MAX / 2 + 1) as $f * 2.0 {
+                    Ordering::Greater
+                } else {
+                    (r as $i).cmp(&v)
+                };
+                directed(r, ord, dir)
+            }
+        }
+    )*)
+}
+
+directed_rule!(f32 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+directed_rule!(f64 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl ToFloatRounded<f64> for f32 {
+    #[inline]
+    fn from_rounded(v: f64, dir: Direction) -> f32 {
+        let r = v as f32;
+        match (r as f64).partial_cmp(&v) {
+            Some(ord) => directed(r, ord, dir),
+            None => r,
+        }
+    }
+}
+
+/// Converts `v` into the float `F`, rounding in `dir` instead of to nearest.
+///
+/// Rounding `Down` and `Up` gives a guaranteed enclosure of the exact value:
+///
+/// ```
+/// # use numtraits::*;
+/// let lo: f32 = to_float_rounded(u64::MAX, Direction::Down);
+/// let hi: f32 = to_float_rounded(u64::MAX, Direction::Up);
+/// assert!((lo as f64) < u64::MAX as f64 && hi == 2f32.powi(64));
+/// ```
+#[inline(always)]
+pub fn to_float_rounded<V, F: ToFloatRounded<V>>(v: V, dir: Direction) -> F {
+    ToFloatRounded::from_rounded(v, dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let p63 = 2f32.powi(63);
+        let below = p63 - 2f32.powi(39);
+        assert_eq!(to_float_rounded::<i64, f32>(i64::MAX, Direction::Up), p63);
+        assert_eq!(to_float_rounded::<i64, f32>(i64::MAX, Direction::Down), below);
+        assert_eq!(to_float_rounded::<i64, f32>(i64::MAX, Direction::TowardZero), below);
+        assert_eq!(to_float_rounded::<i64, f32>(i64::MIN, Direction::Down), -p63);
+        assert_eq!(to_float_rounded::<i64, f32>(i64::MIN + 1, Direction::Down), -p63);
+        assert_eq!(to_float_rounded::<i64, f32>(i64::MIN + 1, Direction::TowardZero), -below);
+        assert_eq!(to_float_rounded::<u32, f32>(16777217, Direction::Up), 16777218.0);
+        assert_eq!(to_float_rounded::<u32, f32>(16777217, Direction::Down), 16777216.0);
+        assert_eq!(to_float_rounded::<u8, f32>(7, Direction::Up), 7.0);
+        assert_eq!(to_float_rounded::<u128, f32>(u128::MAX, Direction::Down), f32::MAX);
+        assert_eq!(to_float_rounded::<u128, f32>(u128::MAX, Direction::Up), f32::INFINITY);
+        assert_eq!(to_float_rounded::<u64, f64>(u64::MAX, Direction::Down),
+                   2f64.powi(64) - 2f64.powi(11));
+    }
+
+    #[test]
+    fn narrowing() {
+        let lo: f32 = to_float_rounded(0.1f64, Direction::Down);
+        let hi: f32 = to_float_rounded(0.1f64, Direction::Up);
+        assert!((lo as f64) < 0.1 && (hi as f64) > 0.1);
+        assert_eq!(hi.to_bits(), lo.to_bits() + 1);
+        assert_eq!(to_float_rounded::<f64, f32>(-0.1, Direction::TowardZero), -lo);
+        assert_eq!(to_float_rounded::<f64, f32>(0.5, Direction::Up), 0.5);
+        assert_eq!(to_float_rounded::<f64, f32>(1e300, Direction::Down), f32::MAX);
+        assert_eq!(to_float_rounded::<f64, f32>(1e300, Direction::Up), f32::INFINITY);
+        assert_eq!(to_float_rounded::<f64, f32>(-1e300, Direction::TowardZero), f32::MIN);
+        assert_eq!(to_float_rounded::<f64, f32>(1e-50, Direction::Down), 0.0);
+        assert_eq!(to_float_rounded::<f64, f32>(1e-50, Direction::Up), f32::from_bits(1));
+        assert!(to_float_rounded::<f64, f32>(f64::NAN, Direction::Up).is_nan());
+    }
+}
@@ -0,0 +1,70 @@
+use core::fmt;
+
+/// Why a checked cast failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CastErrorKind {
+    /// The value is above the maximum of the target type, or for floats, too large in magnitude.
+    Overflow,
+    /// The value is below the minimum of the target type, or for floats, too small in magnitude
+    /// to stay a normal number.
+    Underflow,
+    /// The value is NaN and the target type has no NaN.
+    NaN,
+    /// The value is infinite and the target type has no infinity.
+    Infinite,
+    /// The value has a fractional part and the target type is an integer.
+    Fractional,
+    /// The value is in range but the target type cannot represent it exactly.
+    PrecisionLoss,
+}
+
+impl CastErrorKind {
+    fn description(&self) -> &'static str {
+        match *self {
+            CastErrorKind::Overflow => "value is too large",
+            CastErrorKind::Underflow => "value is too small",
+            CastErrorKind::NaN => "value is NaN",
+            CastErrorKind::Infinite => "value is infinite",
+            CastErrorKind::Fractional => "value has a fractional part",
+            CastErrorKind::PrecisionLoss => "value cannot be represented exactly",
+        }
+    }
+}
+
+/// The error returned by checked casts, such as `try_cast`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CastError {
+    kind: CastErrorKind,
+    from: &'static str,
+    to: &'static str,
+}
+
+impl CastError {
+    /// Creates an error for a cast from the type named `from` to the type named `to`.
+    pub fn new(kind: CastErrorKind, from: &'static str, to: &'static str) -> CastError {
+        CastError { kind, from, to }
+    }
+
+    pub fn kind(&self) -> CastErrorKind {
+        self.kind
+    }
+
+    /// The name of the type being cast from, such as `"u64"`.
+    pub fn from_type(&self) -> &'static str {
+        self.from
+    }
+
+    /// The name of the type being cast to, such as `"u8"`.
+    pub fn to_type(&self) -> &'static str {
+        self.to
+    }
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot cast {} to {}: {}", self.from, self.to, self.kind.description())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
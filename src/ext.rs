@@ -0,0 +1,62 @@
+use crate::{CastError, DownCastAs, LosslessUpCastFrom, SaturatingCastAs, UpCastFrom};
+
+/// The casts as methods, for every type: `x.up::<T>()` is `cast::<_, T>(x)`, and so on.
+///
+/// ```
+/// # use numtraits::*;
+/// fn example<T: UpCastAs<u16> + DownCastAs<u64>>(x: u8, big: u64) -> Result<(T, T), CastError> {
+///     Ok((x.up(), big.try_cast()?))
+/// }
+///
+/// assert_eq!(example::<u32>(7, 1 << 20), Ok((7, 1 << 20)));
+/// assert_eq!(300i32.saturating_cast::<u8>(), 255);
+/// assert_eq!(3u16.cast_lossless::<f32>(), 3.0);
+/// ```
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// let _ = 3u32.up::<i32>(); // Error, u32 is not below i32
+/// ```
+pub trait CastExt: Sized {
+    #[inline(always)]
+    fn up<T: UpCastFrom<Self>>(self) -> T {
+        T::from(self)
+    }
+
+    #[inline(always)]
+    fn try_cast<T: DownCastAs<Self>>(self) -> Result<T, CastError> {
+        T::try_down_from(self)
+    }
+
+    #[inline(always)]
+    fn saturating_cast<T: SaturatingCastAs<Self>>(self) -> T {
+        T::saturating_from(self)
+    }
+
+    #[inline(always)]
+    fn cast_lossless<T: LosslessUpCastFrom<Self>>(self) -> T {
+        T::from_lossless(self)
+    }
+}
+
+impl<V> CastExt for V {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CastErrorKind, UpCastAs};
+
+    fn halve<T: UpCastAs<u8> + DownCastAs<f32>>(x: u8) -> Result<T, CastError> {
+        (x as f32 / 2.0).try_cast()
+    }
+
+    #[test]
+    fn methods() {
+        assert_eq!(200u8.up::<i16>(), 200);
+        assert_eq!(halve::<u16>(8), Ok(4));
+        assert_eq!(halve::<u16>(7).unwrap_err().kind(), CastErrorKind::Fractional);
+        assert_eq!((-1i8).saturating_cast::<u64>(), 0);
+        assert_eq!(f64::NAN.saturating_cast::<i32>(), 0);
+        assert_eq!(u32::MAX.cast_lossless::<f64>(), 4294967295.0);
+    }
+}
@@ -0,0 +1,276 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::{CastError, CastErrorKind};
+
+/// `2^n`, for `n` in the range of normal `f64`s.
+fn pow2(n: i32) -> f64 {
+    f64::from_bits(((n + 1023) as u64) << 52)
+}
+
+/// Rounds `m * 2^k` to the nearest value with `e_bits` of exponent and `m_bits` of mantissa, ties
+/// to even, and gives its bits without the sign. Too large values become infinite.
+fn encode(m: u128, k: i32, e_bits: u32, m_bits: u32) -> u32 {
+    if m == 0 {
+        return 0;
+    }
+    let bias = (1 << (e_bits - 1)) - 1;
+    let e = 127 - m.leading_zeros() as i32 + k;
+    if e > bias {
+        return ((1 << e_bits) - 1) << m_bits;
+    }
+    // Below the normal range the spacing stays that of the smallest exponent.
+    let e = e.max(1 - bias);
+    // `m` is shifted so one unit of the result is one unit in the last place.
+    let s = e - m_bits as i32 - k;
+    let r = if s <= 0 {
+        m << -s
+    } else if s > 128 {
+        0
+    } else {
+        let (q, rem) = if s == 128 { (0, m) } else { (m >> s, m & ((1 << s) - 1)) };
+        let half = 1 << (s - 1);
+        if rem > half || (rem == half && q & 1 == 1) { q + 1 } else { q }
+    };
+    // A normal `r` carries the implicit bit, which adds one to the exponent field. Rounding up
+    // into the next binade, or to infinity, carries over the same way.
+    (((e + bias - 1) as u32) << m_bits) + r as u32
+}
+
+macro_rules! half_float {
+    ($($(#[$attr:meta])* $t:ident $e_bits:literal $m_bits:literal;)*) => ($(
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default)]
+        pub struct $t(u16);
+
+        impl $t {
+            const SIGN: u16 = 1 << ($e_bits + $m_bits);
+            const EXP: u16 = ((1 << $e_bits) - 1) << $m_bits;
+            const BIAS: i32 = (1 << ($e_bits - 1)) - 1;
+
+            /// The largest finite value.
+            pub const MAX: $t = $t(Self::EXP - 1);
+            /// The most negative finite value.
+            pub const MIN: $t = $t(Self::SIGN | (Self::EXP - 1));
+            pub const INFINITY: $t = $t(Self::EXP);
+            pub const NEG_INFINITY: $t = $t(Self::SIGN | Self::EXP);
+            pub const NAN: $t = $t(Self::EXP | 1 << ($m_bits - 1));
+
+            #[inline]
+            pub const fn from_bits(bits: u16) -> $t {
+                $t(bits)
+            }
+
+            #[inline]
+            pub const fn to_bits(self) -> u16 {
+                self.0
+            }
+
+            pub fn is_nan(self) -> bool {
+                self.0 & Self::EXP == Self::EXP && self.0 & !(Self::SIGN | Self::EXP) != 0
+            }
+
+            pub fn is_infinite(self) -> bool {
+                self.0 & !Self::SIGN == Self::EXP
+            }
+
+            /// Neither zero, subnormal, infinite nor NaN.
+            pub fn is_normal(self) -> bool {
+                self.0 & Self::EXP != 0 && self.0 & Self::EXP != Self::EXP
+            }
+
+            /// The nearest value to `x`, ties to even.
+            pub fn from_f64(x: f64) -> $t {
+                let bits = x.to_bits();
+                let sign = if bits >> 63 == 1 { Self::SIGN } else { 0 };
+                let exp = (bits >> 52) as i32 & 0x7ff;
+                let frac = bits & ((1 << 52) - 1);
+                let abs = if exp == 0x7ff {
+                    if frac == 0 { Self::EXP } else { Self::NAN.0 }
+                } else if exp == 0 {
+                    encode(frac as u128, -1074, $e_bits, $m_bits) as u16
+                } else {
+                    encode((frac | 1 << 52) as u128, exp - 1075, $e_bits, $m_bits) as u16
+                };
+                $t(sign | abs)
+            }
+
+            /// The nearest value to `x`, ties to even.
+            #[inline]
+            pub fn from_f32(x: f32) -> $t {
+                $t::from_f64(x as f64)
+            }
+
+            /// The nearest value to `x`, ties to even.
+            pub(crate) fn from_i128(x: i128) -> $t {
+                let sign = if x < 0 { Self::SIGN } else { 0 };
+                $t(sign | encode(x.unsigned_abs(), 0, $e_bits, $m_bits) as u16)
+            }
+
+            pub fn to_f64(self) -> f64 {
+                let exp = ((self.0 & Self::EXP) >> $m_bits) as i32;
+                let frac = (self.0 & !(Self::SIGN | Self::EXP)) as f64;
+                let abs = if exp == (Self::EXP >> $m_bits) as i32 {
+                    if frac == 0.0 { f64::INFINITY } else { f64::NAN }
+                } else if exp == 0 {
+                    frac * pow2(1 - Self::BIAS - $m_bits)
+                } else {
+                    (frac + pow2($m_bits)) * pow2(exp - Self::BIAS - $m_bits)
+                };
+                if self.0 & Self::SIGN == 0 { abs } else { -abs }
+            }
+
+            /// Every value is exact in a `f32`.
+            #[inline]
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// Rounds `x` to the nearest value, and classifies what was lost as
+            /// `narrow_f32_lossy` does.
+            pub fn from_f64_lossy(x: f64) -> ($t, Option<CastErrorKind>) {
+                let r = $t::from_f64(x);
+                let loss = if r.to_f64() == x || x.is_nan() {
+                    None
+                } else if r.is_infinite() {
+                    Some(CastErrorKind::Overflow)
+                } else if !r.is_normal() {
+                    Some(CastErrorKind::Underflow)
+                } else {
+                    Some(CastErrorKind::PrecisionLoss)
+                };
+                (r, loss)
+            }
+
+            pub(crate) fn try_narrow(x: f64, from: &'static str) -> Result<$t, CastError> {
+                match $t::from_f64_lossy(x) {
+                    (r, None) => Ok(r),
+                    (_, Some(kind)) => Err(CastError::new(kind, from, stringify!($t))),
+                }
+            }
+
+            /// Converts `x`, failing unless the result is exact.
+            #[inline]
+            pub fn try_from_f64(x: f64) -> Result<$t, CastError> {
+                $t::try_narrow(x, "f64")
+            }
+
+            /// Converts `x`, failing unless the result is exact.
+            #[inline]
+            pub fn try_from_f32(x: f32) -> Result<$t, CastError> {
+                $t::try_narrow(x as f64, "f32")
+            }
+        }
+
+        impl PartialEq for $t {
+            #[inline]
+            fn eq(&self, other: &$t) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $t {
+            #[inline]
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+    )*)
+}
+
+half_float! {
+    /// IEEE 754 half precision: 5 bits of exponent and 11 significant bits. Its largest value is
+    /// 65504, so it only sits above `u8` and `i8`.
+    F16 5 10;
+    /// bfloat16: the exponent of a `f32` with 8 significant bits, so it covers nearly the range of
+    /// a `f32` at a much lower precision.
+    BF16 8 7;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cast, cast_lossless, try_cast, UpCastAs};
+
+    #[test]
+    fn f16_values() {
+        assert_eq!(F16::from_f32(1.0).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(-2.0).to_bits(), 0xc000);
+        assert_eq!(F16::MAX.to_f32(), 65504.0);
+        assert_eq!(F16::from_bits(1).to_f64(), 2f64.powi(-24));
+        assert_eq!(F16::from_f64(65519.0), F16::MAX);
+        assert!(F16::from_f64(65520.0).is_infinite());
+        assert!(F16::from_f32(f32::NAN).is_nan());
+        assert_eq!(F16::from_f32(-0.0).to_bits(), 0x8000);
+        // Ties go to even: 2049 is halfway between 2048 and 2050.
+        assert_eq!(F16::from_f32(2049.0).to_f32(), 2048.0);
+        assert_eq!(F16::from_f32(2051.0).to_f32(), 2052.0);
+        // Halfway between zero and the smallest subnormal.
+        assert_eq!(F16::from_f64(2f64.powi(-25)).to_bits(), 0);
+        assert_eq!(F16::from_f64(2f64.powi(-25) * 1.5).to_bits(), 1);
+    }
+
+    #[test]
+    fn bf16_values() {
+        assert_eq!(BF16::from_f32(1.0).to_bits(), 0x3f80);
+        assert_eq!(BF16::MAX.to_f32(), f32::from_bits(0x7f7f_0000));
+        assert_eq!(BF16::from_f32(f32::MAX), BF16::INFINITY);
+        assert_eq!(BF16::from_f32(257.0).to_f32(), 256.0);
+        assert_eq!(BF16::from_f32(259.0).to_f32(), 260.0);
+        assert_eq!(BF16::from_bits(1).to_f32(), f32::from_bits(1 << 16));
+        for bits in [0u16, 0x0001, 0x007f, 0x0080, 0x3f80, 0x7f7f, 0x8001, 0xff7f] {
+            assert_eq!(BF16::from_f32(BF16::from_bits(bits).to_f32()).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn f16_round_trips() {
+        for bits in (0..0x7c00).chain(0x8000..0xfc00) {
+            let x = F16::from_bits(bits);
+            assert_eq!(F16::from_f32(x.to_f32()).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(F16::try_from_f32(0.5), Ok(F16::from_f32(0.5)));
+        assert_eq!(F16::try_from_f32(1e5).unwrap_err().kind(), CastErrorKind::Overflow);
+        assert_eq!(F16::try_from_f64(1e-10).unwrap_err().kind(), CastErrorKind::Underflow);
+        assert_eq!(BF16::try_from_f32(0.1).unwrap_err().kind(), CastErrorKind::PrecisionLoss);
+        assert_eq!(BF16::try_from_f64(1e300),
+                   Err(CastError::new(CastErrorKind::Overflow, "f64", "BF16")));
+    }
+
+    fn from_f32<T: UpCastAs<f32>>(x: F16, y: BF16) -> (T, T) {
+        (cast(x), cast(y))
+    }
+
+    #[test]
+    fn lattice() {
+        assert_eq!(cast::<u8, F16>(255).to_f32(), 255.0);
+        assert_eq!(cast::<i8, F16>(-128).to_f32(), -128.0);
+        assert_eq!(cast_lossless::<u8, BF16>(255).to_f32(), 255.0);
+        assert_eq!(cast::<BF16, f64>(BF16::MIN), -f32::from_bits(0x7f7f_0000) as f64);
+        assert_eq!(cast::<F16, BF16>(F16::from_f32(2049.0)).to_f32(), 2048.0);
+        assert_eq!(from_f32::<f64>(F16::MAX, BF16::from_f32(0.5)), (65504.0, 0.5));
+        assert_eq!(cast_lossless::<F16, f32>(F16::MAX), 65504.0);
+        assert_eq!(try_cast::<F16, u8>(F16::from_f32(200.0)), Ok(200));
+        assert_eq!(try_cast::<F16, i8>(F16::from_f32(200.0)).unwrap_err().kind(),
+                   CastErrorKind::Overflow);
+        assert_eq!(try_cast::<f32, F16>(0.25), Ok(F16::from_f32(0.25)));
+        assert_eq!(try_cast::<BF16, F16>(BF16::from_f32(1e10)).unwrap_err().kind(),
+                   CastErrorKind::Overflow);
+    }
+}
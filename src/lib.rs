@@ -1,128 +1,1374 @@
 //! This module defines a trait `UpCastAs<T>` which allows one to upcast (as in only types which make sense
 //! and can fit it another are allowed) between primitive types. These follow a simple hierarchy:
 //!
+//! ```text
+//! f64 > u128 > u64 > u32 > u16 > u8
+//! f64 > f32 > i128 > i64 > i32 > i16 > i8
+//! f32 > u64
+//! i16 > u8, i32 > u16, i64 > u32, i128 > u64
 //! ```
-//! f64 > f32 > u64 > u32 > u16 > u8
-//! f64 > f32 > i64 > i32 > i16 > i8
+//!
+//! Signed and unsigned types only mix one way: an unsigned type fits in the next wider signed
+//! type, so there are also the edges `i16 > u8`, `i32 > u16`, `i64 > u32` and `i128 > u64`.
+//! Nothing signed fits in an unsigned type. You can see these as implication rules, as in a type
+//! which is `UpCastAs<u64>` implies it can be cast from `u32` since `u64 > u32`, and a type which
+//! is `UpCastAs<i32>` implies it can be cast from `u16` since `i32 > u16`. And in this scheme,
+//! `UpCastAs<f64>` means it can be cast from a `f64`, which would mean it can be up cast from any
+//! number type.
+//!
+//! `UpCastAs` is a "range fits" relation: every value of the smaller type is inside the range of
+//! the bigger one, but it may not be represented exactly. `u64 > f32` holds because `u64::MAX` is
+//! well below `f32::MAX`, even though a `u64` above 2^24 gets rounded on the way.
+//!
+//! The 128 bit integers are where the range of `f32` runs out. `i128` fits, since its magnitude
+//! is at most 2^127, but `u128::MAX` rounds up to 2^128 which is past `f32::MAX` and becomes
+//! infinity. So `u128` only sits below `f64`.
+//!
+//! # Pointer sized integers
+//!
+//! `usize` and `isize` are placed by the width of the target they are compiled for, so the edges
+//! only exist where they really hold. Every target has `usize > u16` and `isize > i16`, and both
+//! sit below `u64`/`i64`. On a 64 bit target `usize` also sits above `u32` and `u64`. On a 32
+//! bit target `usize` and `u32` are interchangeable, with edges both ways, and `u32 > usize` also
+//! holds on a 16 bit target. `isize` is placed against `i32` and `i64` the same way.
+//!
+//! ```text
+//! u64 > usize > u16 > u8
+//! i64 > isize > i16 > i8
+//! isize > u8
+//! ```
+//!
+//! `PortableUpCastAs<T>` only has the edges which hold on every supported pointer width (16, 32
+//! and 64 bits), which are the ones drawn above. Code which bounds on it compiles everywhere:
+//!
+//! ```
+//! # use numtraits::*;
+//! fn index<T: PortableUpCastAs<usize>>(v: u16) -> T {
+//!     cast(v)
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use numtraits::*;
+//! fn index<T: PortableUpCastAs<usize>>(v: u32) -> T {
+//!     cast(v) // Error, u32 > usize on a 16 bit target
+//! }
+//! ```
+//!
+//! # Lossless casts
+//!
+//! When rounding is not acceptable, use `LosslessUpCastAs<T>` and `cast_lossless` instead. It only
+//! has the edges which round-trip exactly, which turns the hierarchy into a lattice where the
+//! floats sit beside the integers instead of above them:
+//!
+//! ```text
+//! u128 > u64 > u32 > u16 > u8
+//! i128 > i64 > i32 > i16 > i8
+//! i16 > u8, i32 > u16, i64 > u32, i128 > u64
+//! f64 > f32 > u16, i16
+//! f64 > u32, i32
+//! ```
+//!
+//! ```
+//! # use numtraits::*;
+//! fn example<T: LosslessUpCastAs<f32>>() {
+//!     let _: T = cast_lossless(10f32);
+//!     let _: T = cast_lossless(10u16);
+//!     let _: T = cast_lossless(10i8);
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use numtraits::*;
+//! fn example<T: LosslessUpCastAs<f32>>() {
+//!     let _: T = cast_lossless(10u32); // Error, not every u32 is exact in a f32.
+//! }
+//! ```
+//!
+//! # Checked down casts
+//!
+//! Every edge can also be walked backwards with `DownCastAs<T>` and `try_cast`, which check the
+//! value at runtime and return a `CastError` saying why it did not fit:
+//!
 //! ```
+//! # use numtraits::*;
+//! fn narrow<T: DownCastAs<u64>>(v: u64) -> Result<T, CastError> {
+//!     try_cast(v)
+//! }
+//!
+//! assert_eq!(narrow::<u16>(1000), Ok(1000));
+//! assert_eq!(narrow::<u8>(1000).unwrap_err().kind(), CastErrorKind::Overflow);
+//! ```
+//!
+//! # Saturating casts
+//!
+//! `saturating_cast` goes between any two primitives, in either direction, and clamps values
+//! which do not fit instead of failing. NaN becomes zero when the target is an integer.
+//!
+//! ```
+//! # use numtraits::*;
+//! assert_eq!(saturating_cast::<i64, u8>(-40), 0);
+//! assert_eq!(saturating_cast::<f32, i8>(1000.0), 127);
+//! ```
+//!
+//! # Rounding
 //!
-//! Signed and unsigned types don't mix well. You can see these as implication rules, as in a type
-//! which is `UpCastAs<u64>` implies it can be cast from `u32` since `u64 > u32`. And in this
-//! scheme, `UpCastAs<f64>` means it can be cast from a `f64`, which would mean it can be up cast
-//! from any number type.
+//! `as` truncates floats going into integers. `round_cast` takes a `Rounding` mode instead, and
+//! covers every integer type from both `f32` and `f64`. It comes with a saturating variant,
+//! `saturating_round_cast`, and `exact_cast` which refuses values with a fractional part:
+//!
+//! ```
+//! # use numtraits::*;
+//! let n: Result<i64, _> = round_cast(-2.5f64, Rounding::Floor);
+//! assert_eq!(n, Ok(-3));
+//! assert_eq!(exact_cast::<f64, u32>(42.0), Ok(42));
+//! ```
+//!
+//! Going the other way, `to_float_rounded` converts any integer into `f32` or `f64`, and `f64`
+//! into `f32`, rounding in a chosen `Direction`. Rounding `Down` and `Up` gives guaranteed lower
+//! and upper bounds of the exact value:
+//!
+//! ```
+//! # use numtraits::*;
+//! let lo: f32 = to_float_rounded(16777217u32, Direction::Down);
+//! let hi: f32 = to_float_rounded(16777217u32, Direction::Up);
+//! assert_eq!((lo, hi), (16777216.0, 16777218.0));
+//! ```
+//!
+//! Narrowing a `f64` into a `f32` can lose a value in several ways, which `try_narrow_f32` tells
+//! apart: overflow to infinity, underflow to zero or a subnormal, and plain rounding.
+//! `narrow_f32_lossy` always gives the rounded value along with what was lost.
+//!
+//! # Promotion
+//!
+//! Any two primitives of the hierarchy have a smallest type they both fit in, `Promoted<A, B>`,
+//! and `promote` up casts a pair of values into it. The half precision floats and the
+//! `Wrapping`, `Saturating` and `NonZero*` types are not promoted:
+//!
+//! ```
+//! # use numtraits::*;
+//! fn sum<A: Promote<B>, B>(a: A, b: B) -> Promoted<A, B>
+//! where
+//!     Promoted<A, B>: core::ops::Add<Output = Promoted<A, B>>,
+//! {
+//!     let (a, b) = promote(a, b);
+//!     a + b
+//! }
+//!
+//! assert_eq!(sum(3u8, -2i16), 1i16);
+//! assert_eq!(sum(2i64, 0.5f32), 2.5f32);
+//! ```
+//!
+//! That is what `add_promoted` and the other `*_promoted` functions do, and wrapping both sides in
+//! `Mixed` gives the operators:
+//!
+//! ```
+//! # use numtraits::*;
+//! let x: i16 = Mixed(3u8) + Mixed(-2i16);
+//! let y: f32 = mul_promoted(x, 0.5f32);
+//! assert_eq!(y, 0.5);
+//! ```
 //!
 //! # Examples
 //!
 //! Examples of `cast`:
 //!
 //! ```
+//! # use numtraits::*;
 //! fn example<T: UpCastAs<u32>>() {
 //!     let _: T = cast(10u8);
 //!     let _ = cast::<u8, T>(10u8); // Alternate syntax, uglier.
 //!     let _: T = cast(10u16);
 //!     let _: T = cast(10u32);
+//! }
+//! ```
+//!
+//! ```
+//! # use numtraits::*;
+//! fn example<T: UpCastAs<i32>>() {
+//!     let _: T = cast(10i16);
+//!     let _: T = cast(10u16); // Every u16 fits in an i32.
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use numtraits::*;
+//! fn example<T: UpCastAs<u32>>() {
 //!     let _: T = cast(10u64); // Error, u64 > u32
 //!     let _: T = cast(10f32); // Error, f32 > u32
-//!     let _: T = cast(10f64); // Error, f32 > u32
+//!     let _: T = cast(10f64); // Error, f64 > u32
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use numtraits::*;
+//! fn example<T: UpCastAs<i32>>() {
+//!     let _: T = cast(10u32); // Error, u32 does not fit in an i32
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use numtraits::*;
+//! fn example<T: UpCastAs<f32>>() {
+//!     let _: T = cast(10u128); // Error, u128::MAX overflows a f32
 //! }
 //! ```
 //!
-//! `cast` is just a thin wrapper around `UpCastAs::from`:
+//! `cast` is just a thin wrapper around `UpCastFrom::from`. `UpCastAs<T>` is not a trait with
+//! methods of its own, it is the bundle of `UpCastFrom` for `T` and everything below it, so calling
+//! `from` directly on `T` follows the implication rules just the same. So does the method style
+//! `up_into` from `UpCastInto`, which is to `UpCastFrom` what `Into` is to `From`:
 //!
 //! ```
+//! # use numtraits::*;
 //! fn example<T: UpCastAs<u32>>() {
-//!     let _: T = UpCastAs::from(10u8);
-//!     let _: T = UpCastAs::from(10u16);
+//!     let _: T = UpCastFrom::from(10u8);
+//!     let _: T = UpCastFrom::from(10u16);
+//!     let _ = T::from(10u8);
+//!     let _ = T::from(10u16);
+//!     let _ = T::from(10u32);
+//!     let _: T = 10u16.up_into();
 //!     // ...
 //! }
 //! ```
-//! 
-//! You can also call from directly from `T`, **but it will not follow the implication rules**, it'll
-//! only recognize casting from `V` if `T: UpCastAs<V>`, so this is **not recommended**:
-//! 
+//!
+//! ```compile_fail
+//! # use numtraits::*;
+//! fn example<T: UpCastAs<u32>>() {
+//!     let _ = T::from(10u64); // Error, u64 > u32
+//! }
 //! ```
+//!
+//! ```compile_fail
+//! # use numtraits::*;
 //! fn example<T: UpCastAs<u32>>() {
-//!     let _ = T::from(10u16); // Error
-//!     let _ = T::from(10u32);
-//!     let _ = T::from(10u64); // Error.
+//!     let _: T = 10i8.up_into(); // Error, i8 is not below u32
 //! }
 //! ```
-macro_rules! from_to {
-    ($tr:ident, $f:ident, $t:ident) => {
-        impl $tr<$f> for $t {
-            fn from(x: $f) -> $t { x as $t }
+//!
+//! `CastExt` gives every cast as a method, with the target as a turbofish instead:
+//!
+//! ```
+//! # use numtraits::*;
+//! assert_eq!(10u8.up::<u32>(), 10);
+//! assert_eq!(1000u64.try_cast::<u8>().unwrap_err().kind(), CastErrorKind::Overflow);
+//! assert_eq!((-3i64).saturating_cast::<u16>(), 0);
+//! assert_eq!(10u16.cast_lossless::<f32>(), 10.0);
+//! ```
+//!
+//! The dual bound, "`T` fits in a `u32`", is `FitsIn<u32>`. It implies `UpCastInto` for `u32` and
+//! everything above it:
+//!
+//! ```
+//! # use numtraits::*;
+//! fn example<T: FitsIn<u32>>(t: T) -> u64 {
+//!     t.up_into()
+//! }
+//! ```
+//!
+//! # Categories
+//!
+//! `Unsigned`, `Signed` (both `Integer`) and `Float` bound on a kind of primitive, and carry its
+//! constants:
+//!
+//! ```
+//! # use numtraits::*;
+//! fn span<T: Integer>() -> (T, T) {
+//!     (T::MIN, T::MAX)
+//! }
+//!
+//! assert_eq!(span::<i8>(), (-128, 127));
+//! assert_eq!(<f32 as Float>::MAX_EXACT_INT, 16777216.0);
+//! ```
+//!
+//! `ToUnsigned` and `ToSigned` cross between the two integer pyramids, giving the counterpart of
+//! the same width along with `unsigned_abs`, `abs_diff` and `checked_to_signed`.
+//!
+//! For arithmetic, every primitive of the hierarchy is `Num`, which bundles the operators with
+//! `Zero`, `One`, `PartialOrd` and `Copy`, while the half precision floats and the `Wrapping`,
+//! `Saturating` and `NonZero*` types are not. `T: Num + UpCastAs<u16>` is a number which can take
+//! in any `u16`.
+//!
+//! `Widen` and `Narrow` go between a type and the one twice as wide, and `widening_mul` and
+//! `carrying_add` build multi-precision arithmetic on top of them:
+//!
+//! ```
+//! # use numtraits::*;
+//! assert_eq!(widening_mul(u64::MAX, 2), u64::MAX as u128 * 2);
+//! assert_eq!(0x1234u16.split(), (0x12, 0x34));
+//! assert_eq!(carrying_add(u8::MAX, 1, 0), (0, 1));
+//! ```
+//!
+//! # Wrappers
+//!
+//! `Wrapping<T>` and `Saturating<T>` have the edges of `T`, so `Wrapping<u16>:
+//! UpCastAs<Wrapping<u8>>`. The `NonZero*` integers have the edges of their integers, which keep
+//! the value non-zero, and cast losslessly into everything their integer does:
+//!
+//! ```
+//! # use numtraits::*;
+//! use core::num::{NonZeroU16, NonZeroU8, Wrapping};
+//!
+//! let x: Wrapping<i32> = cast(Wrapping(200u8));
+//! let n: NonZeroU16 = cast(NonZeroU8::new(7).unwrap());
+//! let f: f32 = cast_lossless(n);
+//! assert_eq!((x.0, f), (200, 7.0));
+//! ```
+//!
+//! # Half precision
+//!
+//! The `half` feature adds the software floats `F16` and `BF16`. `F16` only reaches 65504, while
+//! `BF16` has the exponent of a `f32`:
+//!
+//! ```text
+//! f32 > BF16 > F16 > u8, i8
+//! ```
+//!
+//! Up casting into them rounds to nearest, and `try_cast` from `f32` or `f64` fails unless the
+//! value is exact, so `try_cast::<f32, F16>(x)` is the checked conversion. `F16::from_f32` and
+//! `F16::from_f64_lossy` round instead, the latter telling what was lost like `narrow_f32_lossy`.
+//!
+//! # Newtypes
+//!
+//! With the `derive` feature, `#[derive(UpCastAs)]` puts a newtype such as `struct Meters(f64)`
+//! where the type it wraps is, so `Meters: UpCastAs<f64>`. An `#[up_cast(max = T)]` or
+//! `#[up_cast(only(A, B))]` attribute narrows down the types it can be built from.
+//!
+//! Types which are not a plain wrapper, like a decimal or a fixed point type, are placed with
+//! `lattice!` instead, by naming the type right below them and the types they fit in. It checks
+//! that the declaration is consistent with the rest of the hierarchy when it compiles.
+//!
+//! A type which only has a std `From<B>` can still be passed where a `UpCastAs<B>` is expected,
+//! wrapped in `ViaStd<T, B>`, and `cast_via_std` casts into it directly. `UpCastAs` has more
+//! edges than std's `From`, which only has the exact ones: `i64` up casts into `f32`, but there is
+//! no `From<i64>` for `f32`.
+//!
+//! # Const casts
+//!
+//! Trait methods cannot be called in a `const` context yet, so `cast_const!` does the same up
+//! casts for primitives, checking the edge at compile time:
+//!
+//! ```
+//! # use numtraits::cast_const;
+//! const BITS: u32 = 12;
+//! const LEN: usize = cast_const!(u16 => usize, 1 << BITS);
+//! static LOOKUP: [f32; LEN] = [0.0; LEN];
+//! ```
+//!
+//! # `no_std`
+//!
+//! The crate is `no_std`. Enable the `std` feature to get the `std::error::Error` impl of
+//! `CastError`.
+
+#![no_std]
+
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+// Checked conversion of the float `$t: $b` into the integer type `$a`. `MAX / 2 + 1` is a power
+// of two, so doubling it gives the exclusive upper bound exactly, where `MAX` itself could round
+// up.
+macro_rules! float_to_int {
+    ($b:ident $a:ident $t:expr) => (float_to_int!(@named stringify!($b), $b $a $t));
+    // `$name` is the source type in errors, when `$t` is the value of some other type as a `$b`.
+    (@named $name:expr, $b:ident $a:ident $t:expr) => ({
+        let t: $b = $t;
+        let err = |kind| Err(CastError::new(kind, $name, stringify!($a)));
+        if t.is_nan() {
+            err(CastErrorKind::NaN)
+        } else if t.is_infinite() {
+            err(CastErrorKind::Infinite)
+        } else if t >= (<$a>::MAX / 2 + 1) as $b * 2.0 {
+            err(CastErrorKind::Overflow)
+        } else if t < <$a>::MIN as $b {
+            err(CastErrorKind::Underflow)
+        } else if (t as $a) as $b != t {
+            err(CastErrorKind::Fractional)
+        } else {
+            Ok(t as $a)
         }
+    })
+}
+
+mod bridge;
+mod category;
+mod directed;
+mod error;
+mod ext;
+#[cfg(feature = "half")]
+mod half;
+mod mixed;
+mod narrow;
+mod num;
+mod promote;
+mod round;
+mod saturating;
+mod sign;
+mod widen;
+mod wrapper;
+
+pub use bridge::{cast_via_std, ViaStd};
+pub use category::{Float, Integer, Signed, Unsigned};
+#[doc(hidden)]
+pub use category::Primitive as __Primitive;
+pub use directed::{to_float_rounded, Direction, ToFloatRounded};
+pub use error::{CastError, CastErrorKind};
+pub use ext::CastExt;
+#[cfg(feature = "half")]
+pub use half::{BF16, F16};
+pub use mixed::{add_promoted, div_promoted, mul_promoted, rem_promoted, sub_promoted, Mixed};
+pub use narrow::{narrow_f32_lossy, try_narrow_f32};
+pub use num::{Bounded, Num, One, Zero};
+pub use promote::{promote, Promote, Promoted};
+pub use round::{exact_cast, round_cast, saturating_round_cast, RoundCastAs, Rounding};
+pub use saturating::{saturating_cast, SaturatingCastAs};
+pub use sign::{ToSigned, ToUnsigned};
+pub use widen::{carrying_add, widening_mul, Narrow, Widen};
+
+#[cfg(feature = "derive")]
+pub use numtraits_derive::UpCastAs;
+
+/// Marker for the "range fits" relation used by `UpCastAs`.
+pub enum Range {}
+
+/// Marker for the range relation without the edges of the pointer sized integers which depend
+/// on the target, used by `PortableUpCastAs`.
+pub enum Portable {}
+
+/// Marker for the exact relation used by `LosslessUpCastAs`.
+pub enum Lossless {}
+
+/// Marker for the reverse of the range relation, used by `FitsIn`.
+pub enum Fits {}
+
+/// Lists every type which `Self` can be built from under the relation `R`, besides `Self`. For
+/// `Fits` it goes the other way, and lists every type which can be built from `Self`.
+///
+/// Unused slots are filled with `Self`. This is what lets a bound like `T: UpCastAs<u32>` imply
+/// `T: UpCastFrom<u16>` even though `u16` sits below more than one type.
+pub trait Lattice<R> {
+    type L0; type L1; type L2; type L3; type L4; type L5; type L6; type L7;
+    type L8; type L9; type L10; type L11; type L12; type L13; type L14; type L15;
+    type L16; type L17; type L18; type L19; type L20; type L21; type L22; type L23;
+    type L24; type L25; type L26; type L27; type L28; type L29; type L30; type L31;
+}
+
+/// A single edge of the range relation: `Self` can hold every value of `V`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be up cast from `{V}`",
+    label = "not every `{V}` fits in `{Self}`",
+    note = "use `try_cast` or `saturating_cast` for casts which can fail"
+)]
+pub trait UpCastFrom<V>: Sized {
+    fn from(v: V) -> Self;
+}
+
+/// The other side of `UpCastFrom`, for method style calls: `v.up_into()`.
+///
+/// It is implemented for every `V` such that `T: UpCastFrom<V>`, and should not be implemented
+/// directly.
+pub trait UpCastInto<T> {
+    fn up_into(self) -> T;
+}
+
+impl<V, T: UpCastFrom<V>> UpCastInto<T> for V {
+    #[inline(always)]
+    fn up_into(self) -> T {
+        T::from(self)
     }
 }
 
-pub trait UpCastAs<T> {
-    fn from(T) -> Self;
+/// A single edge of the lossless relation: `Self` can hold every value of `V` exactly.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be cast losslessly from `{V}`",
+    label = "not every `{V}` is exact in `{Self}`"
+)]
+pub trait LosslessUpCastFrom<V>: Sized {
+    fn from_lossless(v: V) -> Self;
+}
+
+/// The reverse of an `UpCastFrom` edge: `Self` can hold some values of `T`, which is checked.
+///
+/// There is one impl for every edge of the range relation, so `u8: DownCastAs<u64>` and
+/// `u32: DownCastAs<f32>`, but not `u32: DownCastAs<i8>`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be down cast from `{T}`",
+    label = "`{Self}` is not below `{T}`",
+    note = "use `saturating_cast` between types which are not related"
+)]
+pub trait DownCastAs<T>: Sized {
+    fn try_down_from(t: T) -> Result<Self, CastError>;
+}
+
+macro_rules! relation {
+    ($(#[$attr:meta])* trait $name:ident: $from:ident<$rel:ident>) => (
+        relation!(@slots $(#[$attr])* $name $from $rel [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ]);
+    );
+    (@slots $(#[$attr:meta])* $name:ident $from:ident $rel:ident [$($slot:ident)*]) => (
+        $(#[$attr])*
+        pub trait $name<T: Lattice<$rel>>: $from<T> $(+ $from<T::$slot>)* {}
+
+        impl<U, T: Lattice<$rel>> $name<T> for U where U: $from<T> $(+ $from<T::$slot>)* {}
+    )
+}
+
+relation! {
+    /// `Self` can be up cast from `T` and from everything below `T` in the range hierarchy.
+    trait UpCastAs: UpCastFrom<Range>
+}
+
+relation! {
+    /// Like `UpCastAs`, but only with the edges which hold on every supported pointer width, so
+    /// `T: PortableUpCastAs<usize>` implies `T: UpCastFrom<u16>` and not `T: UpCastFrom<u32>`.
+    trait PortableUpCastAs: UpCastFrom<Portable>
+}
+
+relation! {
+    /// `Self` fits in `T` and in everything above `T` in the range hierarchy, the dual of
+    /// `UpCastAs`. `up_into` gives the wider value.
+    ///
+    /// ```
+    /// # use numtraits::*;
+    /// fn widen<T: FitsIn<u32> + Copy>(t: T) -> (u32, u64, f64) {
+    ///     (t.up_into(), t.up_into(), t.up_into())
+    /// }
+    ///
+    /// assert_eq!(widen(7u16), (7, 7, 7.0));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use numtraits::*;
+    /// fn widen<T: FitsIn<u32>>(t: T) -> i32 {
+    ///     t.up_into() // Error, u32 does not fit in i32
+    /// }
+    /// ```
+    trait FitsIn: UpCastInto<Fits>
+}
+
+relation! {
+    /// `Self` can be exactly up cast from `T` and from everything below `T` in the lossless lattice.
+    trait LosslessUpCastAs: LosslessUpCastFrom<Lossless>
+}
+
+// Expands to the first block when both primitives are the same type, and to the second otherwise.
+macro_rules! if_same {
+    (u8 u8 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (u16 u16 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (u32 u32 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (u64 u64 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (u128 u128 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (usize usize {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (i8 i8 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (i16 i16 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (i32 i32 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (i64 i64 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (i128 i128 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (isize isize {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (f32 f32 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (f64 f64 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (F16 F16 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    (BF16 BF16 {$($y:tt)*} {$($n:tt)*}) => ($($y)*);
+    ($a:ident $b:ident {$($y:tt)*} {$($n:tt)*}) => ($($n)*);
+}
+
+macro_rules! plain {
+    ($t:ident) => ($t);
+}
+
+macro_rules! nonzero {
+    (u8) => (core::num::NonZeroU8);
+    (u16) => (core::num::NonZeroU16);
+    (u32) => (core::num::NonZeroU32);
+    (u64) => (core::num::NonZeroU64);
+    (u128) => (core::num::NonZeroU128);
+    (usize) => (core::num::NonZeroUsize);
+    (i8) => (core::num::NonZeroI8);
+    (i16) => (core::num::NonZeroI16);
+    (i32) => (core::num::NonZeroI32);
+    (i64) => (core::num::NonZeroI64);
+    (i128) => (core::num::NonZeroI128);
+    (isize) => (core::num::NonZeroIsize);
 }
 
 macro_rules! cast_rule {
-    ($b:ident as $a:ident) => (
-        impl UpCastAs<$a> for $b {
+    // Tag every source with the `cfg` predicate it exists under. `(usize if "32" "64")` means the
+    // edge only exists on those pointer widths, and `(F16 with "half")` only with the `half`
+    // feature.
+    (@norm $k:tt [$($done:tt)*]) => (
+        cast_rule!(@gen $k $($done)*);
+    );
+    (@norm $k:tt [$($done:tt)*] , $($rest:tt)*) => (
+        cast_rule!(@norm $k [$($done)*] $($rest)*);
+    );
+    (@norm $k:tt [$($done:tt)*] ($a:ident if $($w:literal)+) $($rest:tt)*) => (
+        cast_rule!(@norm $k [$($done)* {$a (any($(target_pointer_width = $w),+))}] $($rest)*);
+    );
+    (@norm $k:tt [$($done:tt)*] ($a:ident with $f:literal) $($rest:tt)*) => (
+        cast_rule!(@norm $k [$($done)* {$a (feature = $f)}] $($rest)*);
+    );
+    (@norm $k:tt [$($done:tt)*] $a:ident $($rest:tt)*) => (
+        cast_rule!(@norm $k [$($done)* {$a (all())}] $($rest)*);
+    );
+    (@gen ($tr:ident $f:ident $rel:ident $b:ident) $({$a:ident $p:tt})*) => (
+        impl $tr<$b> for $b {
             #[inline(always)]
-            fn from(t: $a) -> $b { t as $b }
+            fn $f(t: $b) -> $b { t }
         }
+        $(
+            #[cfg $p]
+            impl $tr<$a> for $b {
+                #[inline(always)]
+                fn $f(t: $a) -> $b { cast_rule!(@as $a $b t) }
+            }
+        )*
+        cast_rule!(@fill plain $rel $b; [$({$a $p})*]; [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ];);
+        cast_rule!(@down $rel $b; $({$a $p})*);
+        cast_rule!(@nonzero $rel $b; $({$a $p})*);
     );
-    ($a:ident => $b:ident) => (
-        impl<U: UpCastAs<$a>> UpCastAs<$b> for U {
+    // `as` for the primitives. The half precision floats go through `f32`, which holds all of
+    // their values, or round the integer directly.
+    (@as F16 BF16 $t:ident) => (BF16::from_f32($t.to_f32()));
+    (@as F16 $b:ident $t:ident) => ($t.to_f32() as $b);
+    (@as BF16 $b:ident $t:ident) => ($t.to_f32() as $b);
+    (@as $a:ident F16 $t:ident) => (F16::from_i128($t as i128));
+    (@as $a:ident BF16 $t:ident) => (BF16::from_i128($t as i128));
+    (@as $a:ident $b:ident $t:ident) => ($t as $b);
+    // Only the range relation gets reverse edges, the lossless ones are a subset of them.
+    (@down Lossless $b:ident; $($x:tt)*) => ();
+    (@down Range $b:ident; $({$a:ident $p:tt})*) => (
+        impl DownCastAs<$b> for $b {
             #[inline(always)]
-            fn from(t: $b) -> U { U::from(t as $a) }
+            fn try_down_from(t: $b) -> Result<$b, CastError> { Ok(t) }
         }
+        $(
+            #[cfg $p]
+            impl DownCastAs<$b> for $a {
+                #[inline]
+                fn try_down_from(t: $b) -> Result<$a, CastError> {
+                    cast_rule!(@check $b $a t)
+                }
+            }
+        )*
+    );
+    (@err $b:ident $a:ident $kind:ident) => (
+        Err(CastError::new(CastErrorKind::$kind, stringify!($b), stringify!($a)))
     );
-    (self $a:ident) => (
-        impl UpCastAs<$a> for $a {
+    (@check f64 f32 $t:ident) => (try_narrow_f32($t));
+    (@check f32 F16 $t:ident) => (F16::try_from_f32($t));
+    (@check f64 F16 $t:ident) => (F16::try_from_f64($t));
+    (@check BF16 F16 $t:ident) => (F16::try_narrow($t.to_f64(), "BF16"));
+    (@check f32 BF16 $t:ident) => (BF16::try_from_f32($t));
+    (@check f64 BF16 $t:ident) => (BF16::try_from_f64($t));
+    (@check F16 $a:ident $t:ident) => (float_to_int!(@named "F16", f32 $a $t.to_f32()));
+    (@check BF16 $a:ident $t:ident) => (float_to_int!(@named "BF16", f32 $a $t.to_f32()));
+    (@check f32 $a:ident $t:ident) => (float_to_int!(f32 $a $t));
+    (@check f64 $a:ident $t:ident) => (float_to_int!(f64 $a $t));
+    // Integer to integer. `$a` fits in `$b`, so the bounds of `$a` are exact in `$b` and the
+    // value round trips exactly when it is in range.
+    (@check $b:ident $a:ident $t:ident) => ({
+        let r = $t as $a;
+        if r as $b == $t {
+            Ok(r)
+        } else if $t > <$a>::MAX as $b {
+            cast_rule!(@err $b $a Overflow)
+        } else {
+            cast_rule!(@err $b $a Underflow)
+        }
+    });
+    // `$m` maps every type of the row, to fill in the impl for `plain` types or `nonzero` ones.
+    (@fill $m:ident $rel:ident $b:ident; []; [$($slot:ident)*]; $($body:tt)*) => (
+        impl Lattice<$rel> for $m!($b) {
+            $($body)*
+            $(type $slot = $m!($b);)*
+        }
+    );
+    (@fill $m:ident $rel:ident $b:ident; [{$a:ident $p:tt} $($rest:tt)*];
+     [$slot:ident $($slots:ident)*]; $($body:tt)*) => (
+        cast_rule!(@fill $m $rel $b; [$($rest)*]; [$($slots)*];
+                   $($body)* #[cfg $p] type $slot = $m!($a); #[cfg(not $p)] type $slot = $m!($b););
+    );
+    // The `NonZero*` types have the same range edges as their integers, and cast losslessly into
+    // everything their integer does.
+    (@nonzero Lossless F16; $($x:tt)*) => ();
+    (@nonzero Lossless BF16; $($x:tt)*) => ();
+    (@nonzero Lossless $b:ident; $($x:tt)*) => (
+        cast_rule!(@nonzero_into $b; {$b (all())} $($x)*);
+    );
+    (@nonzero $rel:ident f32; $($x:tt)*) => ();
+    (@nonzero $rel:ident f64; $($x:tt)*) => ();
+    (@nonzero $rel:ident F16; $($x:tt)*) => ();
+    (@nonzero $rel:ident BF16; $($x:tt)*) => ();
+    (@nonzero Range $b:ident; $({$a:ident $p:tt})*) => (
+        impl UpCastFrom<nonzero!($b)> for nonzero!($b) {
+            #[inline(always)]
+            fn from(t: nonzero!($b)) -> nonzero!($b) { t }
+        }
+        $(
+            #[cfg $p]
+            impl UpCastFrom<nonzero!($a)> for nonzero!($b) {
+                #[inline(always)]
+                fn from(t: nonzero!($a)) -> nonzero!($b) {
+                    // Up casting between integers keeps the value, so it stays non-zero.
+                    <nonzero!($b)>::new(t.get() as $b).unwrap()
+                }
+            }
+        )*
+        cast_rule!(@fill nonzero Range $b; [$({$a $p})*]; [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ];);
+    );
+    (@nonzero Portable $b:ident; $($x:tt)*) => (
+        cast_rule!(@fill nonzero Portable $b; [$($x)*]; [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ];);
+    );
+    (@nonzero_into $b:ident;) => ();
+    (@nonzero_into $b:ident; {f32 $p:tt} $($rest:tt)*) => (
+        cast_rule!(@nonzero_into $b; $($rest)*);
+    );
+    (@nonzero_into $b:ident; {f64 $p:tt} $($rest:tt)*) => (
+        cast_rule!(@nonzero_into $b; $($rest)*);
+    );
+    (@nonzero_into $b:ident; {F16 $p:tt} $($rest:tt)*) => (
+        cast_rule!(@nonzero_into $b; $($rest)*);
+    );
+    (@nonzero_into $b:ident; {BF16 $p:tt} $($rest:tt)*) => (
+        cast_rule!(@nonzero_into $b; $($rest)*);
+    );
+    (@nonzero_into $b:ident; {$a:ident $p:tt} $($rest:tt)*) => (
+        #[cfg $p]
+        impl LosslessUpCastFrom<nonzero!($a)> for $b {
             #[inline(always)]
-            fn from(t: $a) -> $a { t }
+            fn from_lossless(t: nonzero!($a)) -> $b { t.get() as $b }
+        }
+        cast_rule!(@nonzero_into $b; $($rest)*);
+    );
+    // The reverse of the range relation, for `FitsIn`. Every row of the table takes a slot in
+    // the `Lattice<Fits>` impl of `$x`, holding the row's type if `$x` is listed in it.
+    (@fits $x:ident [$($rows:tt)*]) => (
+        cast_rule!(@fits_slots $x [$($rows)*] [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ] []);
+    );
+    (@fits_slots $x:ident [] [$($slot:ident)*] [$($body:tt)*]) => (
+        impl Lattice<Fits> for $x {
+            $($body)*
+            $(type $slot = $x;)*
         }
+    );
+    (@fits_slots $x:ident [$w:ident $(=> $($a:tt),+)?; $($rows:tt)*]
+     [$slot:ident $($slots:ident)*] [$($body:tt)*]) => (
+        cast_rule!(@fits_slots $x [$($rows)*] [$($slots)*]
+                   [$($body)* cast_rule!(@member $x $slot $w $($($a)*)?);]);
+    );
+    (@member $x:ident $slot:ident $w:ident) => (
+        type $slot = $x;
+    );
+    (@member $x:ident $slot:ident $w:ident ($a:ident if $($p:literal)+) $($rest:tt)*) => (
+        if_same!($x $a {
+            #[cfg(any($(target_pointer_width = $p),+))]
+            type $slot = $w;
+            #[cfg(not(any($(target_pointer_width = $p),+)))]
+            type $slot = $x;
+        } {
+            cast_rule!(@member $x $slot $w $($rest)*);
+        });
+    );
+    (@member $x:ident $slot:ident $w:ident ($a:ident with $f:literal) $($rest:tt)*) => (
+        if_same!($x $a {
+            #[cfg(feature = $f)]
+            type $slot = $w;
+            #[cfg(not(feature = $f))]
+            type $slot = $x;
+        } {
+            cast_rule!(@member $x $slot $w $($rest)*);
+        });
+    );
+    (@member $x:ident $slot:ident $w:ident $a:ident $($rest:tt)*) => (
+        if_same!($x $a {
+            type $slot = $w;
+        } {
+            cast_rule!(@member $x $slot $w $($rest)*);
+        });
+    );
+    // The `Portable` lattice is the range one without the sources which depend on the pointer
+    // width. It only needs the slots, the edges it keeps are all there already.
+    (@portable $b:ident [$($done:tt)*]) => (
+        cast_rule!(@fill plain Portable $b; [$($done)*]; [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ];);
+        cast_rule!(@nonzero Portable $b; $($done)*);
+    );
+    (@portable $b:ident [$($done:tt)*] , $($rest:tt)*) => (
+        cast_rule!(@portable $b [$($done)*] $($rest)*);
+    );
+    (@portable $b:ident [$($done:tt)*] ($a:ident if $($w:literal)+) $($rest:tt)*) => (
+        cast_rule!(@portable $b [$($done)*] $($rest)*);
+    );
+    (@portable $b:ident [$($done:tt)*] ($a:ident with $f:literal) $($rest:tt)*) => (
+        cast_rule!(@portable $b [$($done)* {$a (feature = $f)}] $($rest)*);
+    );
+    (@portable $b:ident [$($done:tt)*] $a:ident $($rest:tt)*) => (
+        cast_rule!(@portable $b [$($done)* {$a (all())}] $($rest)*);
+    );
+    (@range $table:tt $($b:ident $(=> $($a:tt),+)?;)*) => (
+        $(cast_rule!(@norm (UpCastFrom from Range $b) [] $($($a),+)?);)*
+        $(cast_rule!(@portable $b [] $($($a),+)?);)*
+        $(cast_rule!(@fits $b $table);)*
+    );
+    (lossless $($b:ident $(=> $($a:tt),+)?;)*) => (
+        $(cast_rule!(@norm (LosslessUpCastFrom from_lossless Lossless $b) [] $($($a),+)?);)*
+    );
+    ($($rows:tt)*) => (
+        cast_rule!(@range [$($rows)*] $($rows)*);
     )
 }
 
-cast_rule!(self u8);
-cast_rule!(self u16);
-cast_rule!(self u32);
-cast_rule!(self u64);
+// Appends the rows of the half precision floats to a table when the `half` feature is on. Only
+// `u8` and `i8` go below them, so that the feature does not add types above the wider integers,
+// which would break `lattice!` declarations listing those.
+#[cfg(feature = "half")]
+macro_rules! with_half {
+    (lossless $($rows:tt)*) => (
+        cast_rule! {
+            lossless $($rows)*
+            F16 => u8, i8;
+            BF16 => u8, i8;
+        }
+    );
+    ($($rows:tt)*) => (
+        cast_rule! {
+            $($rows)*
+            F16 => u8, i8;
+            BF16 => F16, u8, i8;
+        }
+    );
+}
 
-cast_rule!(self i8);
-cast_rule!(self i16);
-cast_rule!(self i32);
-cast_rule!(self i64);
+#[cfg(not(feature = "half"))]
+macro_rules! with_half {
+    ($($rows:tt)*) => (cast_rule! { $($rows)* });
+}
 
-cast_rule!(self f32);
-cast_rule!(self f64);
+// Implications. Each rule lists everything below a type, not only its direct children, since
+// every listed type gets its own `UpCastFrom` impl. The `FitsIn` lattice is read off the same
+// table backwards.
+with_half! {
+    u8;
+    u16 => u8, (usize if "16");
+    u32 => u16, u8, (usize if "16" "32");
+    u64 => u32, u16, u8, usize;
+    u128 => u64, u32, u16, u8, usize;
+    usize => u16, u8, (u32 if "32" "64"), (u64 if "64");
 
-// cast_rule!(u8 as u16);
-// cast_rule!(u8 as u32);
-// cast_rule!(u8 as u64);
-// cast_rule!(u8 as f32);
-// cast_rule!(u8 as f64);
+    i8;
+    i16 => i8, u8, (isize if "16");
+    i32 => i16, i8, u16, u8, (isize if "16" "32"), (usize if "16");
+    i64 => i32, i16, i8, u32, u16, u8, isize, (usize if "16" "32");
+    i128 => i64, i32, i16, i8, u64, u32, u16, u8, isize, usize;
+    isize => i16, i8, u8, (i32 if "32" "64"), (u16 if "32" "64"), (i64 if "64"), (u32 if "64");
 
-// Implications. Pyramid.
-cast_rule!(i16 => i8);
-cast_rule!(i32 => i16);
-cast_rule!(i64 => i32);
+    f32 => u64, u32, u16, u8, usize, i128, i64, i32, i16, i8, isize,
+           (F16 with "half"), (BF16 with "half");
+    f64 => f32, u128, u64, u32, u16, u8, usize, i128, i64, i32, i16, i8, isize,
+           (F16 with "half"), (BF16 with "half");
+}
+
+// Lossless implications. f32 has a 24 bit significand and f64 a 53 bit one.
+with_half! {
+    lossless
+    u8;
+    u16 => u8, (usize if "16");
+    u32 => u16, u8, (usize if "16" "32");
+    u64 => u32, u16, u8, usize;
+    u128 => u64, u32, u16, u8, usize;
+    usize => u16, u8, (u32 if "32" "64"), (u64 if "64");
 
-cast_rule!(u16 => u8);
-cast_rule!(u32 => u16);
-cast_rule!(u64 => u32);
+    i8;
+    i16 => i8, u8, (isize if "16");
+    i32 => i16, i8, u16, u8, (isize if "16" "32"), (usize if "16");
+    i64 => i32, i16, i8, u32, u16, u8, isize, (usize if "16" "32");
+    i128 => i64, i32, i16, i8, u64, u32, u16, u8, isize, usize;
+    isize => i16, i8, u8, (i32 if "32" "64"), (u16 if "32" "64"), (i64 if "64"), (u32 if "64");
 
-cast_rule!(f32 => i64);
-cast_rule!(f32 => u64);
-cast_rule!(f64 => f32);
+    f32 => u16, u8, i16, i8, (usize if "16"), (isize if "16"),
+           (F16 with "half"), (BF16 with "half");
+    f64 => f32, u32, u16, u8, i32, i16, i8, (usize if "16" "32"), (isize if "16" "32"),
+           (F16 with "half"), (BF16 with "half");
+}
 
 #[inline(always)]
-pub fn cast<V, T: UpCastAs<V>>(v: V) -> T {
-    UpCastAs::from(v)
+pub fn cast<V, T: UpCastFrom<V>>(v: V) -> T {
+    UpCastFrom::from(v)
+}
+
+/// Down casts `v` into `T`, or says why the value does not fit.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(try_cast::<u64, u8>(200), Ok(200));
+/// assert_eq!(try_cast::<u64, u8>(300).unwrap_err().kind(), CastErrorKind::Overflow);
+/// assert_eq!(try_cast::<f64, i32>(2.5).unwrap_err().kind(), CastErrorKind::Fractional);
+/// ```
+#[inline(always)]
+pub fn try_cast<V, T: DownCastAs<V>>(v: V) -> Result<T, CastError> {
+    DownCastAs::try_down_from(v)
+}
+
+#[inline(always)]
+pub fn cast_lossless<V, T: LosslessUpCastFrom<V>>(v: V) -> T {
+    LosslessUpCastFrom::from_lossless(v)
+}
+
+#[doc(hidden)]
+pub const fn __up_cast_edge<V, T>()
+where
+    V: __Primitive,
+    T: __Primitive + UpCastFrom<V>,
+{
+}
+
+#[doc(hidden)]
+pub const fn __lossless_up_cast_edge<V, T>()
+where
+    V: __Primitive,
+    T: __Primitive + LosslessUpCastFrom<V>,
+{
+}
+
+/// Up casts a primitive in a `const` context, following the same rules as `cast`, or those of
+/// `cast_lossless` with `lossless` in front. Both types must be primitives, since the conversion
+/// is an `as` cast: the `NonZero` and `Wrapping` edges, `F16` and the types of `lattice!` go
+/// through `cast` instead.
+///
+/// ```
+/// # use numtraits::cast_const;
+/// const LEN: usize = cast_const!(u16 => usize, 300);
+/// const HALF: f64 = cast_const!(lossless u32 => f64, 1 << 31) / 2.0;
+/// static TABLE: [u8; LEN] = [0; LEN];
+/// ```
+///
+/// ```compile_fail
+/// # use numtraits::cast_const;
+/// const LEN: u32 = cast_const!(u64 => u32, 300); // Error, u64 > u32
+/// ```
+///
+/// A fieldless enum placed with `lattice!` would get through `as` with its discriminant instead of
+/// the conversion it was given, so it is rejected too:
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// #[derive(Clone, Copy)]
+/// #[repr(u8)]
+/// enum Level { High = 7 }
+///
+/// lattice! {
+///     Level {
+///         above: u8 => |_: u8| Level::High;
+///         below: u128 => |_: Level| 100, f64 => |_: Level| 100.0;
+///     }
+/// }
+///
+/// const X: u128 = cast_const!(Level => u128, Level::High); // Error, Level is not a primitive
+/// ```
+#[macro_export]
+macro_rules! cast_const {
+    (lossless $v:ty => $t:ty, $x:expr) => ({
+        $crate::__lossless_up_cast_edge::<$v, $t>();
+        let v: $v = $x;
+        v as $t
+    });
+    ($v:ty => $t:ty, $x:expr) => ({
+        $crate::__up_cast_edge::<$v, $t>();
+        let v: $v = $x;
+        v as $t
+    })
+}
+
+/// Places types of your own in the hierarchy, each with the one type right below it and the
+/// types it fits in:
+///
+/// ```
+/// # use numtraits::*;
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// pub struct Decimal(i64);
+///
+/// lattice! {
+///     Decimal {
+///         above: i64 => |x: i64| Decimal(x);
+///         below: f32 => |d: Decimal| d.0 as f32, f64 => |d: Decimal| d.0 as f64;
+///     }
+/// }
+///
+/// fn example<T: UpCastAs<Decimal>>() -> T {
+///     cast(7u32)
+/// }
+///
+/// assert_eq!(example::<Decimal>(), Decimal(7));
+/// assert_eq!(example::<f64>(), 7.0);
+/// ```
+///
+/// `Decimal` can then be built from everything below `i64` through the function given with
+/// `above`, and `T: UpCastAs<Decimal>` implies `T` can be built from `Decimal` and from everything
+/// below it. The types listed with `below` must include everything above them too, so they can be
+/// read off as `FitsIn`, and all of them must be above the one given with `above`. Any other
+/// declaration does not compile:
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// pub struct Decimal(i64);
+///
+/// lattice! {
+///     Decimal {
+///         above: i64 => |x: i64| Decimal(x);
+///         below: f32 => |d: Decimal| d.0 as f32; // Error, f64 is above f32 and is missing
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// pub struct Decimal(i64);
+///
+/// lattice! {
+///     Decimal {
+///         above: i64 => |x: i64| Decimal(x);
+///         below: i64 => |d: Decimal| d.0, f32 => |d: Decimal| d.0 as f32,
+///                f64 => |d: Decimal| d.0 as f64; // Error, `i64` is on both sides
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use numtraits::*;
+/// pub struct Decimal(i64);
+///
+/// lattice! {
+///     Decimal {
+///         above: i64 => |x: i64| Decimal(x);
+///         below: u64 => |d: Decimal| d.0 as u64; // Error, u64 is not above i64
+///     }
+/// }
+/// ```
+///
+/// The primitives are closed though: `UpCastAs<f64>` still only implies the edges of the
+/// primitives, even though `f64` can now be built from a `Decimal`.
+#[macro_export]
+macro_rules! lattice {
+    ($($name:ident {
+        above: $a:ty => $from:expr;
+        $(below: $($b:ty => $into:expr),+ $(,)?;)?
+    })*) => ($(
+        impl<V> $crate::UpCastFrom<V> for $name where $a: $crate::UpCastFrom<V> {
+            #[inline(always)]
+            fn from(v: V) -> $name {
+                let from: fn($a) -> $name = $from;
+                from(<$a as $crate::UpCastFrom<V>>::from(v))
+            }
+        }
+
+        impl $crate::UpCastFrom<$name> for $name {
+            #[inline(always)]
+            fn from(v: $name) -> $name { v }
+        }
+
+        $($(
+            impl $crate::UpCastFrom<$name> for $b {
+                #[inline(always)]
+                fn from(v: $name) -> $b {
+                    let into: fn($name) -> $b = $into;
+                    into(v)
+                }
+            }
+        )+)?
+
+        impl $crate::Lattice<$crate::Range> for $name {
+            type L0 = $a;
+            $crate::lattice!(@below $a, $crate::Range [
+                L1 L0 L2 L1 L3 L2 L4 L3 L5 L4 L6 L5 L7 L6 L8 L7 L9 L8 L10 L9 L11 L10 L12 L11
+                L13 L12 L14 L13 L15 L14 L16 L15 L17 L16 L18 L17 L19 L18 L20 L19 L21 L20
+                L22 L21 L23 L22 L24 L23 L25 L24 L26 L25 L27 L26 L28 L27 L29 L28 L30 L29 L31 L30
+            ]);
+        }
+
+        impl $crate::Lattice<$crate::Portable> for $name {
+            type L0 = $a;
+            $crate::lattice!(@below $a, $crate::Portable [
+                L1 L0 L2 L1 L3 L2 L4 L3 L5 L4 L6 L5 L7 L6 L8 L7 L9 L8 L10 L9 L11 L10 L12 L11
+                L13 L12 L14 L13 L15 L14 L16 L15 L17 L16 L18 L17 L19 L18 L20 L19 L21 L20
+                L22 L21 L23 L22 L24 L23 L25 L24 L26 L25 L27 L26 L28 L27 L29 L28 L30 L29 L31 L30
+            ]);
+        }
+
+        $crate::lattice!(@above $name [] [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ] $($($b),+)?);
+
+        const _: () = {
+            // Every type listed must be above `$a`, and the list must be closed upwards.
+            fn above<T: $crate::UpCastAs<$a>>() {}
+            fn fits<T: $crate::FitsIn<U>, U: $crate::Lattice<$crate::Fits>>() {}
+            $($(
+                let _ = above::<$b>;
+                let _ = fits::<$name, $b>;
+            )+)?
+
+            // A type listed twice, or on both sides, gives conflicting impls of this.
+            #[allow(dead_code)]
+            trait NoCycle {}
+            impl NoCycle for $a {}
+            $($(impl NoCycle for $b {})+)?
+        };
+    )*);
+    // Slot `L{n}` of the new type holds slot `L{n - 1}` of the type right below it.
+    (@below $a:ty, $rel:ty [$($slot:ident $prev:ident)*]) => (
+        $(type $slot = <$a as $crate::Lattice<$rel>>::$prev;)*
+    );
+    (@above $name:ident [$($body:tt)*] [$($slot:ident)*]) => (
+        impl $crate::Lattice<$crate::Fits> for $name {
+            $($body)*
+            $(type $slot = $name;)*
+        }
+    );
+    (@above $name:ident [$($body:tt)*] [$slot:ident $($slots:ident)*] $b:ty $(, $rest:ty)*) => (
+        $crate::lattice!(@above $name [$($body)* type $slot = $b;] [$($slots)*] $($rest),*);
+    );
 }
 
 #[cfg(test)]
 fn doit<T: UpCastAs<u64>>() {
     let _ = T::from(10u64);
-    // let y = T::from(10u8); // Error.
+    let _ = T::from(10u8); // Follows the implication rules too.
     let _: T = cast(10u16); // Works for all types upscalable up to `B` where `T: UpCastAs<B>`
     // let _: T = cast(10f32); // Error
     let _ = cast::<u16, T>(10u16); // Alternate syntax.
-    let _: T = UpCastAs::from(10u8); // Works for all types as well.
+    let _: T = UpCastFrom::from(10u8); // Works for all types as well.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::num::{NonZeroI32, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU8};
+    use std::string::ToString;
+
+    fn exact<T: LosslessUpCastAs<f64>>(v: u32) -> T {
+        cast_lossless(v)
+    }
+
+    fn every_way<T: UpCastAs<i64>>() -> [T; 10] {
+        [
+            T::from(1i64), T::from(2i32), T::from(3i16), T::from(4i8), T::from(5u32),
+            T::from(6u16), T::from(7u8), T::from(8isize), 9u32.up_into(), 10i8.up_into(),
+        ]
+    }
+
+    #[test]
+    fn from_follows_implications() {
+        assert_eq!(every_way::<i64>(), [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(every_way::<f64>()[9], 10.0);
+    }
+
+    fn wider<T: FitsIn<u16> + Copy>(t: T) -> (u32, i32, f32, usize, u16) {
+        (t.up_into(), t.up_into(), t.up_into(), t.up_into(), t.up_into())
+    }
+
+    #[test]
+    fn fits_in() {
+        assert_eq!(wider(200u8), (200, 200, 200.0, 200, 200));
+        assert_eq!(wider(u16::MAX), (65535, 65535, 65535.0, 65535, 65535));
+    }
+
+    #[test]
+    fn implications() {
+        super::doit::<u64>();
+        super::doit::<f32>();
+        assert_eq!(cast::<u8, f64>(200), 200.0);
+        assert_eq!(cast::<i16, i64>(-3), -3);
+    }
+
+    fn signed<T: UpCastAs<i64>>(v: u32) -> T {
+        cast(v)
+    }
+
+    #[test]
+    fn unsigned_into_signed() {
+        assert_eq!(signed::<i64>(u32::MAX), 4294967295);
+        assert_eq!(cast::<u16, i32>(u16::MAX), 65535);
+        assert_eq!(cast::<u8, i16>(u8::MAX), 255);
+        assert_eq!(cast_lossless::<u32, i64>(u32::MAX), 4294967295);
+    }
+
+    fn accumulate<T: UpCastAs<i128>>(v: i64, w: u64) -> (T, T) {
+        (cast(v), cast(w))
+    }
+
+    #[test]
+    fn wide_integers() {
+        assert_eq!(accumulate::<i128>(i64::MIN, u64::MAX), (i64::MIN as i128, u64::MAX as i128));
+        assert_eq!(cast::<u64, u128>(u64::MAX), u64::MAX as u128);
+        assert_eq!(cast::<i128, f32>(i128::MIN), -2f32.powi(127));
+        assert!((u128::MAX as f32).is_infinite());
+        assert_eq!(cast::<u128, f64>(u128::MAX), 2f64.powi(128));
+    }
+
+    #[test]
+    fn pointer_sized() {
+        assert_eq!(cast::<usize, u64>(usize::MAX), usize::MAX as u64);
+        assert_eq!(cast::<u16, usize>(u16::MAX), 65535);
+        assert_eq!(cast::<isize, i128>(isize::MIN), isize::MIN as i128);
+        assert_eq!(cast::<u8, isize>(200), 200);
+        assert_eq!(cast_lossless::<usize, u128>(usize::MAX), usize::MAX as u128);
+    }
+
+    #[test]
+    fn portable() {
+        fn index<T: PortableUpCastAs<usize>>(v: u16) -> T {
+            cast(v)
+        }
+        assert_eq!(index::<usize>(u16::MAX), 65535);
+        assert_eq!(index::<u64>(7), 7);
+        assert_eq!(index::<i128>(7), 7);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn pointer_sized_64() {
+        fn index<T: UpCastAs<usize>>(v: u32) -> T {
+            cast(v)
+        }
+        assert_eq!(index::<usize>(u32::MAX), u32::MAX as usize);
+        assert_eq!(cast::<u64, usize>(u64::MAX), usize::MAX);
+        assert_eq!(cast::<u32, isize>(u32::MAX), u32::MAX as isize);
+    }
+
+    fn store<T: DownCastAs<i64>>(v: i64) -> Result<T, CastErrorKind> {
+        try_cast(v).map_err(|e: CastError| e.kind())
+    }
+
+    #[test]
+    fn down_casts() {
+        assert_eq!(store::<u8>(255), Ok(255));
+        assert_eq!(store::<u8>(256), Err(CastErrorKind::Overflow));
+        assert_eq!(store::<u32>(-1), Err(CastErrorKind::Underflow));
+        assert_eq!(store::<i16>(i16::MIN as i64 - 1), Err(CastErrorKind::Underflow));
+        assert_eq!(store::<i64>(i64::MIN), Ok(i64::MIN));
+        assert_eq!(try_cast::<i128, u64>(u64::MAX as i128), Ok(u64::MAX));
+        assert_eq!(try_cast::<i128, u64>(u64::MAX as i128 + 1).unwrap_err().kind(),
+                   CastErrorKind::Overflow);
+        // The method does not shadow `TryFrom` from the prelude.
+        assert!(u8::try_from(300u64).is_err());
+    }
+
+    #[test]
+    fn float_down_casts() {
+        assert_eq!(try_cast::<f64, u8>(255.0), Ok(255));
+        assert_eq!(try_cast::<f64, u8>(256.0).unwrap_err().kind(), CastErrorKind::Overflow);
+        assert_eq!(try_cast::<f64, u8>(-1.0).unwrap_err().kind(), CastErrorKind::Underflow);
+        assert_eq!(try_cast::<f64, u8>(-0.0), Ok(0));
+        assert_eq!(try_cast::<f32, i64>(i64::MIN as f32), Ok(i64::MIN));
+        assert_eq!(try_cast::<f32, i64>(-(i64::MIN as f32)).unwrap_err().kind(),
+                   CastErrorKind::Overflow);
+        assert_eq!(try_cast::<f32, u32>(0.5).unwrap_err().kind(), CastErrorKind::Fractional);
+        assert_eq!(try_cast::<f32, u32>(f32::NAN).unwrap_err().kind(), CastErrorKind::NaN);
+        assert_eq!(try_cast::<f64, i8>(f64::NEG_INFINITY).unwrap_err().kind(),
+                   CastErrorKind::Infinite);
+
+        assert_eq!(try_cast::<f64, f32>(0.5), Ok(0.5));
+        assert!(try_cast::<f64, f32>(f64::NAN).unwrap().is_nan());
+        assert_eq!(try_cast::<f64, f32>(f64::INFINITY), Ok(f32::INFINITY));
+        assert_eq!(try_cast::<f64, f32>(1e300).unwrap_err().kind(), CastErrorKind::Overflow);
+        assert_eq!(try_cast::<f64, f32>(0.1).unwrap_err().kind(), CastErrorKind::PrecisionLoss);
+        assert_eq!(try_cast::<f64, f32>(1e-300).unwrap_err().kind(), CastErrorKind::Underflow);
+    }
+
+    #[test]
+    fn cast_error_display() {
+        let e = try_cast::<u32, u8>(1000).unwrap_err();
+        assert_eq!((e.from_type(), e.to_type()), ("u32", "u8"));
+        assert_eq!(e.to_string(), "cannot cast u32 to u8: value is too large");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cast_error_is_error() {
+        let e: std::boxed::Box<dyn std::error::Error> = try_cast::<i16, u8>(-1).unwrap_err().into();
+        assert_eq!(e.to_string(), "cannot cast i16 to u8: value is too small");
+    }
+
+    #[test]
+    fn const_casts() {
+        const N: usize = cast_const!(u8 => usize, 3);
+        const BIG: i64 = cast_const!(lossless u32 => i64, u32::MAX);
+        assert_eq!([0u8; N].len(), 3);
+        assert_eq!(BIG, 4294967295);
+    }
+
+    #[test]
+    fn lossless_round_trips() {
+        assert_eq!(exact::<f64>(u32::MAX) as u32, u32::MAX);
+        assert_eq!(cast_lossless::<u16, f32>(u16::MAX) as u16, u16::MAX);
+        assert_eq!(cast_lossless::<i16, f32>(i16::MIN) as i16, i16::MIN);
+        assert_eq!(cast_lossless::<i32, f64>(i32::MIN) as i32, i32::MIN);
+    }
+
+    fn non_zero<T: UpCastAs<NonZeroU16>>(x: NonZeroU8) -> T {
+        cast(x)
+    }
+
+    #[test]
+    fn non_zero_edges() {
+        let x = NonZeroU8::new(200).unwrap();
+        assert_eq!(non_zero::<NonZeroU32>(x).get(), 200);
+        assert_eq!(non_zero::<NonZeroI32>(x).get(), 200);
+        assert_eq!(cast::<_, NonZeroU16>(x).get(), 200);
+        assert_eq!(cast_lossless::<_, u8>(x), 200);
+        assert_eq!(cast_lossless::<_, f32>(x), 200.0);
+        assert_eq!(cast_lossless::<_, i16>(NonZeroI8::new(-5).unwrap()), -5);
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Milli(i64);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Micro(i128);
+
+    lattice! {
+        Milli {
+            above: i32 => |x: i32| Milli(x as i64 * 1000);
+            below: i128 => |m: Milli| m.0 as i128 / 1000, f32 => |m: Milli| m.0 as f32 / 1e3,
+                   f64 => |m: Milli| m.0 as f64 / 1e3;
+        }
+        Micro {
+            above: Milli => |m: Milli| Micro(m.0 as i128 * 1000);
+            below: f64 => |m: Micro| m.0 as f64 / 1e6;
+        }
+    }
+
+    fn from_milli<T: UpCastAs<Milli>>(m: Milli) -> (T, T, T) {
+        (T::from(m), T::from(-2i32), 3u16.up_into())
+    }
+
+    fn into_f64<T: FitsIn<i128>>(t: T) -> f64 {
+        t.up_into()
+    }
+
+    #[test]
+    fn user_lattice() {
+        assert_eq!(from_milli::<Milli>(Milli(1500)), (Milli(1500), Milli(-2000), Milli(3000)));
+        assert_eq!(from_milli::<Micro>(Milli(1)), (Micro(1000), Micro(-2000000), Micro(3000000)));
+        assert_eq!(from_milli::<f64>(Milli(1500)), (1.5, -2.0, 3.0));
+        assert_eq!(into_f64(Milli(250)), 0.25);
+        assert_eq!(cast::<u8, Micro>(1), Micro(1000000));
+    }
 }
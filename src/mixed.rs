@@ -0,0 +1,73 @@
+use core::ops::{Add, Div, Mul, Rem, Sub};
+
+use crate::{promote, Promote, Promoted};
+
+/// Wraps a number so the arithmetic operators work between any two types of the hierarchy. Both
+/// sides are promoted first, and the result is a plain value of the promoted type.
+///
+/// ```
+/// # use numtraits::*;
+/// let x: i16 = Mixed(3u8) + Mixed(-2i16);
+/// assert_eq!(x, 1);
+/// let y: f64 = Mixed(1u128) / Mixed(4f32);
+/// assert_eq!(y, 0.25);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mixed<T>(pub T);
+
+macro_rules! promoted_op {
+    ($($tr:ident $method:ident $f:ident $doc:literal;)*) => ($(
+        #[doc = $doc]
+        #[inline(always)]
+        pub fn $f<A: Promote<B>, B>(a: A, b: B) -> Promoted<A, B>
+        where
+            Promoted<A, B>: $tr<Output = Promoted<A, B>>,
+        {
+            let (a, b) = promote(a, b);
+            a.$method(b)
+        }
+
+        impl<A: Promote<B>, B> $tr<Mixed<B>> for Mixed<A>
+        where
+            Promoted<A, B>: $tr<Output = Promoted<A, B>>,
+        {
+            type Output = Promoted<A, B>;
+
+            #[inline(always)]
+            fn $method(self, rhs: Mixed<B>) -> Promoted<A, B> {
+                $f(self.0, rhs.0)
+            }
+        }
+    )*)
+}
+
+promoted_op! {
+    Add add add_promoted "Adds `a` and `b` in the type both fit in.";
+    Sub sub sub_promoted "Subtracts `b` from `a` in the type both fit in.";
+    Mul mul mul_promoted "Multiplies `a` and `b` in the type both fit in.";
+    Div div div_promoted "Divides `a` by `b` in the type both fit in.";
+    Rem rem rem_promoted "The remainder of `a` divided by `b`, in the type both fit in.";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions() {
+        assert_eq!(add_promoted(3u8, -2i16), 1i16);
+        assert_eq!(sub_promoted(0u32, 1i8), -1i64);
+        assert_eq!(mul_promoted(u64::MAX, -2i8), u64::MAX as i128 * -2);
+        assert_eq!(div_promoted(7i32, 2.0f32), 3.5f32);
+        assert_eq!(rem_promoted(7u16, -4i16), 3i32);
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(Mixed(3u8) + Mixed(-2i16), 1i16);
+        assert_eq!(Mixed(200u8) - Mixed(-100i8), 300i16);
+        assert_eq!(Mixed(1usize) * Mixed(3u16), 3usize);
+        assert_eq!(Mixed(u128::MAX) / Mixed(-1i8), -(u128::MAX as f64));
+        assert_eq!(Mixed(-7i64) % Mixed(3u32), -1i64);
+    }
+}
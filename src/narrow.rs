@@ -0,0 +1,64 @@
+use crate::{CastError, CastErrorKind};
+
+/// Narrows `x` to the nearest `f32`, and classifies what was lost on the way.
+///
+/// The classification is `None` when the result is exact, `Overflow` when a finite value became
+/// infinite, `Underflow` when the result is zero or subnormal and not exact, and `PrecisionLoss`
+/// for any other rounding. NaN and the infinities are always kept.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(narrow_f32_lossy(0.5), (0.5, None));
+/// assert_eq!(narrow_f32_lossy(1e-50), (0.0, Some(CastErrorKind::Underflow)));
+/// ```
+pub fn narrow_f32_lossy(x: f64) -> (f32, Option<CastErrorKind>) {
+    let r = x as f32;
+    let loss = if r as f64 == x || x.is_nan() {
+        None
+    } else if r.is_infinite() {
+        Some(CastErrorKind::Overflow)
+    } else if !r.is_normal() {
+        Some(CastErrorKind::Underflow)
+    } else {
+        Some(CastErrorKind::PrecisionLoss)
+    };
+    (r, loss)
+}
+
+/// Narrows `x` to an `f32`, failing unless the result is exact.
+///
+/// The error kind is classified as in `narrow_f32_lossy`. This is also what
+/// `DownCastAs<f64>` does for `f32`.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(try_narrow_f32(0.25), Ok(0.25));
+/// assert_eq!(try_narrow_f32(1e300).unwrap_err().kind(), CastErrorKind::Overflow);
+/// assert_eq!(try_narrow_f32(0.1).unwrap_err().kind(), CastErrorKind::PrecisionLoss);
+/// ```
+pub fn try_narrow_f32(x: f64) -> Result<f32, CastError> {
+    match narrow_f32_lossy(x) {
+        (r, None) => Ok(r),
+        (_, Some(kind)) => Err(CastError::new(kind, "f64", "f32")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classification() {
+        let tiny = f32::MIN_POSITIVE as f64;
+        assert_eq!(narrow_f32_lossy(tiny), (f32::MIN_POSITIVE, None));
+        assert_eq!(narrow_f32_lossy(tiny / 4.0), (f32::MIN_POSITIVE / 4.0, None));
+        assert_eq!(narrow_f32_lossy(tiny / 3.0).1, Some(CastErrorKind::Underflow));
+        assert_eq!(narrow_f32_lossy(-1e-300), (-0.0, Some(CastErrorKind::Underflow)));
+        assert_eq!(narrow_f32_lossy(-1e300), (f32::NEG_INFINITY, Some(CastErrorKind::Overflow)));
+        assert_eq!(narrow_f32_lossy(f64::INFINITY), (f32::INFINITY, None));
+        assert_eq!(narrow_f32_lossy(16777217.0), (16777216.0, Some(CastErrorKind::PrecisionLoss)));
+        assert_eq!(narrow_f32_lossy(0.0), (0.0, None));
+        assert!(narrow_f32_lossy(f64::NAN).0.is_nan());
+        assert_eq!(narrow_f32_lossy(f64::NAN).1, None);
+    }
+}
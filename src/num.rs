@@ -0,0 +1,117 @@
+use core::ops::{Add, Div, Mul, Rem, Sub};
+
+/// The additive identity.
+pub trait Zero: Sized {
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
+}
+
+/// The multiplicative identity.
+pub trait One: Sized {
+    fn one() -> Self;
+}
+
+/// Types with a smallest and a largest value. For floats these are the finite ones.
+pub trait Bounded: Sized {
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+/// The arithmetic every primitive of the hierarchy has. It is implemented for every type with the
+/// listed supertraits, and combines with the casts, as in `T: Num + UpCastAs<u16>`:
+///
+/// ```
+/// # use numtraits::*;
+/// fn sum<T: Num + UpCastAs<u16>>(xs: &[u16]) -> T {
+///     xs.iter().fold(T::zero(), |acc, &x| acc + cast(x))
+/// }
+///
+/// assert_eq!(sum::<u32>(&[40000, 40000]), 80000);
+/// assert_eq!(sum::<f32>(&[1, 2]), 3.0);
+/// ```
+pub trait Num:
+    Copy
+    + PartialOrd
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+}
+
+impl<T> Num for T where
+    T: Copy
+        + PartialOrd
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+{
+}
+
+macro_rules! num {
+    ($($t:ident $zero:literal $one:literal;)*) => ($(
+        impl Zero for $t {
+            #[inline(always)]
+            fn zero() -> $t { $zero }
+            #[inline(always)]
+            fn is_zero(&self) -> bool { *self == $zero }
+        }
+        impl One for $t {
+            #[inline(always)]
+            fn one() -> $t { $one }
+        }
+        impl Bounded for $t {
+            #[inline(always)]
+            fn min_value() -> $t { <$t>::MIN }
+            #[inline(always)]
+            fn max_value() -> $t { <$t>::MAX }
+        }
+    )*)
+}
+
+num! {
+    u8 0 1; u16 0 1; u32 0 1; u64 0 1; u128 0 1; usize 0 1;
+    i8 0 1; i16 0 1; i32 0 1; i64 0 1; i128 0 1; isize 0 1;
+    f32 0.0 1.0; f64 0.0 1.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cast, UpCastAs};
+
+    fn mean<T: Num + UpCastAs<u16>>(xs: &[u8]) -> T {
+        let sum = xs.iter().fold(T::zero(), |acc, &x| acc + cast(x));
+        sum / cast(xs.len() as u16)
+    }
+
+    fn clamp<T: Num + Bounded>(x: T) -> T {
+        if x < T::zero() { T::zero() } else if x == T::max_value() { T::one() } else { x }
+    }
+
+    #[test]
+    fn generic() {
+        assert_eq!(mean::<u32>(&[255, 255, 3]), 171);
+        assert_eq!(mean::<f64>(&[1, 2]), 1.5);
+        assert_eq!(clamp(-3i8), 0);
+        assert_eq!(clamp(u64::MAX), 1);
+        assert_eq!(clamp(2.5f32), 2.5);
+    }
+
+    #[test]
+    fn identities() {
+        assert!(0u8.is_zero());
+        assert!((-0.0f64).is_zero());
+        assert!(!i128::one().is_zero());
+        assert_eq!(<f32 as Bounded>::min_value(), f32::MIN);
+        assert_eq!(<f32 as Bounded>::max_value(), f32::MAX);
+        assert_eq!(<isize as Bounded>::min_value(), isize::MIN);
+    }
+}
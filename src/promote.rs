@@ -0,0 +1,164 @@
+use crate::UpCastFrom;
+
+/// The least upper bound of `Self` and `B` under the range relation: the smallest type which
+/// both can be up cast into.
+///
+/// It is symmetric, so `<A as Promote<B>>::Output` and `<B as Promote<A>>::Output` are always the
+/// same type. Mixing signedness goes to the next wider signed type, and `u128` with anything
+/// signed or with `f32` goes all the way to `f64`.
+///
+/// Where a pointer sized integer and a fixed width one are equally good, the pointer sized one is
+/// picked, so `Promoted<usize, u64>` is `usize` on a 64 bit target.
+pub trait Promote<B>: Sized {
+    type Output: UpCastFrom<Self> + UpCastFrom<B>;
+}
+
+/// Shorthand for `<A as Promote<B>>::Output`.
+pub type Promoted<A, B> = <A as Promote<B>>::Output;
+
+/// Up casts `a` and `b` into the type both fit in.
+///
+/// ```
+/// # use numtraits::*;
+/// let (a, b) = promote(3u8, -2i16);
+/// assert_eq!(a + b, 1i16);
+/// let (a, b): (f32, f32) = promote(1i64, 0.5f32);
+/// assert_eq!(a + b, 1.5);
+/// ```
+#[inline(always)]
+pub fn promote<A: Promote<B>, B>(a: A, b: B) -> (Promoted<A, B>, Promoted<A, B>) {
+    (UpCastFrom::from(a), UpCastFrom::from(b))
+}
+
+macro_rules! promote_rule {
+    (@row $a:ident [$($b:ident)*] $($out:ident)*) => ($(
+        impl Promote<$b> for $a {
+            type Output = $out;
+        }
+    )*);
+    // Also generates the mirrored impls, for the rows of the pointer sized integers.
+    (@both $a:ident [$($b:ident)*] $($out:ident)*) => ($(
+        impl Promote<$b> for $a {
+            type Output = $out;
+        }
+        impl Promote<$a> for $b {
+            type Output = $out;
+        }
+    )*);
+    (both $cols:tt $($a:ident: $($out:ident)*;)*) => (
+        $(promote_rule!(@both $a $cols $($out)*);)*
+    );
+    ($cols:tt $($a:ident: $($out:ident)*;)*) => (
+        $(promote_rule!(@row $a $cols $($out)*);)*
+    );
+}
+
+// Every entry is the join of its row and its column. The pointer sized integers move around
+// with the target, so they get a table for each width.
+promote_rule! {
+    [      u8    u16   u32   u64   u128  i8    i16   i32   i64   i128  f32   f64]
+    u8:    u8    u16   u32   u64   u128  i16   i16   i32   i64   i128  f32   f64;
+    u16:   u16   u16   u32   u64   u128  i32   i32   i32   i64   i128  f32   f64;
+    u32:   u32   u32   u32   u64   u128  i64   i64   i64   i64   i128  f32   f64;
+    u64:   u64   u64   u64   u64   u128  i128  i128  i128  i128  i128  f32   f64;
+    u128:  u128  u128  u128  u128  u128  f64   f64   f64   f64   f64   f64   f64;
+    i8:    i16   i32   i64   i128  f64   i8    i16   i32   i64   i128  f32   f64;
+    i16:   i16   i32   i64   i128  f64   i16   i16   i32   i64   i128  f32   f64;
+    i32:   i32   i32   i64   i128  f64   i32   i32   i32   i64   i128  f32   f64;
+    i64:   i64   i64   i64   i128  f64   i64   i64   i64   i64   i128  f32   f64;
+    i128:  i128  i128  i128  i128  f64   i128  i128  i128  i128  i128  f32   f64;
+    f32:   f32   f32   f32   f32   f64   f32   f32   f32   f32   f32   f32   f64;
+    f64:   f64   f64   f64   f64   f64   f64   f64   f64   f64   f64   f64   f64;
+}
+
+#[cfg(target_pointer_width = "64")]
+promote_rule! { both
+    [      u8    u16   u32   u64   u128  i8    i16   i32   i64   i128  f32   f64]
+    usize: usize usize usize usize u128  i128  i128  i128  i128  i128  f32   f64;
+    isize: isize isize isize i128  f64   isize isize isize isize i128  f32   f64;
+}
+#[cfg(target_pointer_width = "64")]
+promote_rule! {
+    [      usize isize]
+    usize: usize i128;
+    isize: i128  isize;
+}
+
+#[cfg(target_pointer_width = "32")]
+promote_rule! { both
+    [      u8    u16   u32   u64   u128  i8    i16   i32   i64   i128  f32   f64]
+    usize: usize usize usize u64   u128  i64   i64   i64   i64   i128  f32   f64;
+    isize: isize isize i64   i128  f64   isize isize isize i64   i128  f32   f64;
+}
+#[cfg(target_pointer_width = "32")]
+promote_rule! {
+    [      usize isize]
+    usize: usize i64;
+    isize: i64   isize;
+}
+
+#[cfg(target_pointer_width = "16")]
+promote_rule! { both
+    [      u8    u16   u32   u64   u128  i8    i16   i32   i64   i128  f32   f64]
+    usize: usize usize u32   u64   u128  i32   i32   i32   i64   i128  f32   f64;
+    isize: isize i32   i64   i128  f64   isize isize i32   i64   i128  f32   f64;
+}
+#[cfg(target_pointer_width = "16")]
+promote_rule! {
+    [      usize isize]
+    usize: usize i32;
+    isize: i32   isize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same<T: 'static, U: 'static>() -> bool {
+        core::any::TypeId::of::<T>() == core::any::TypeId::of::<U>()
+    }
+
+    #[test]
+    fn joins() {
+        assert!(same::<Promoted<u8, i16>, i16>());
+        assert!(same::<Promoted<i64, f32>, f32>());
+        assert!(same::<Promoted<u16, i8>, i32>());
+        assert!(same::<Promoted<u64, i64>, i128>());
+        assert!(same::<Promoted<u128, i8>, f64>());
+        assert!(same::<Promoted<u128, f32>, f64>());
+        assert!(same::<Promoted<u32, u32>, u32>());
+        assert!(same::<Promoted<usize, u8>, usize>());
+        assert!(same::<Promoted<isize, i16>, isize>());
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn joins_64() {
+        assert!(same::<Promoted<usize, u32>, usize>());
+        assert!(same::<Promoted<usize, u64>, usize>());
+        assert!(same::<Promoted<isize, u16>, isize>());
+        assert!(same::<Promoted<isize, u32>, isize>());
+    }
+
+    fn symmetric<A: Promote<B> + 'static, B: Promote<A> + 'static>() -> bool {
+        same::<Promoted<A, B>, Promoted<B, A>>()
+    }
+
+    #[test]
+    fn symmetry() {
+        macro_rules! each {
+            ($($a:ident)*) => (each!(@rows [$($a)*] $($a)*));
+            (@rows $all:tt $($a:ident)*) => ($(each!(@row $a $all);)*);
+            (@row $a:ident [$($b:ident)*]) => ($(assert!(symmetric::<$a, $b>());)*);
+        }
+        each!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+    }
+
+    #[test]
+    fn promote_values() {
+        assert_eq!(promote(200u8, -100i8), (200i16, -100i16));
+        assert_eq!(promote(u64::MAX, -1i64), (u64::MAX as i128, -1i128));
+        assert_eq!(promote(2u128, 0.5f32), (2.0f64, 0.5f64));
+        assert_eq!(promote(7usize, 1u16), (7usize, 1usize));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{CastError, CastErrorKind};
+
+/// How a float with a fractional part is turned into an integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Towards negative infinity.
+    Floor,
+    /// Towards positive infinity.
+    Ceil,
+    /// To the nearest integer, ties go to the even one.
+    NearestEven,
+    /// To the nearest integer, ties go away from zero.
+    NearestAway,
+    /// Drops the fractional part, like `as` does.
+    TowardZero,
+}
+
+trait RoundFloat: Sized {
+    /// Rounds to an integral value of the same float type. NaN and the infinities are kept.
+    fn round_with(self, mode: Rounding) -> Self;
+}
+
+macro_rules! round_float {
+    ($f:ident, $i:ident, $limit:expr) => (
+        impl RoundFloat for $f {
+            fn round_with(self, mode: Rounding) -> $f {
+                // From `$limit` on every value is already an integer. Below it the value fits in
+                // `$i`, and both the truncated value and the fractional part are exact.
+                if !(self < $limit && self > -$limit) {
+                    return self;
+                }
+                let t = self as $i;
+                let d = self - t as $f;
+                let step = match mode {
+                    Rounding::Floor => if d < 0.0 { -1 } else { 0 },
+                    Rounding::Ceil => if d > 0.0 { 1 } else { 0 },
+                    Rounding::TowardZero => 0,
+                    Rounding::NearestAway => {
+                        if d >= 0.5 { 1 } else if d <= -0.5 { -1 } else { 0 }
+                    }
+                    Rounding::NearestEven => {
+                        let odd = t % 2 != 0;
+                        if d > 0.5 || (d == 0.5 && odd) {
+                            1
+                        } else if d < -0.5 || (d == -0.5 && odd) {
+                            -1
+                        } else {
+                            0
+                        }
+                    }
+                };
+                (t + step) as $f
+            }
+        }
+    )
+}
+
+round_float!(f32, i32, 8388608.0);
+round_float!(f64, i64, 4503599627370496.0);
+
+/// `Self` is an integer which can be built from the float `F` with an explicit rounding mode.
+pub trait RoundCastAs<F>: Sized {
+    /// Rounds `f` and checks that the result fits.
+    fn try_round_from(f: F, mode: Rounding) -> Result<Self, CastError>;
+    /// Rounds `f` and clamps the result to the bounds of `Self`. NaN becomes zero.
+    fn saturating_round_from(f: F, mode: Rounding) -> Self;
+    /// Fails with `CastErrorKind::Fractional` unless `f` is already an integer.
+    fn exact_from(f: F) -> Result<Self, CastError>;
+}
+
+macro_rules! round_rule {
+    ($f:ident => $($i:ident)*) => ($(
+        impl RoundCastAs<$f> for $i {
+            #[inline]
+            fn try_round_from(f: $f, mode: Rounding) -> Result<$i, CastError> {
+                float_to_int!($f $i f.round_with(mode))
+            }
+
+            #[inline]
+            fn saturating_round_from(f: $f, mode: Rounding) -> $i {
+                f.round_with(mode) as $i
+            }
+
+            #[inline]
+            fn exact_from(f: $f) -> Result<$i, CastError> {
+                float_to_int!($f $i f)
+            }
+        }
+    )*)
+}
+
+round_rule!(f32 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+round_rule!(f64 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Rounds `v` with `mode` and casts it into `T`, failing if the result does not fit.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(round_cast::<f64, i32>(2.5, Rounding::NearestEven), Ok(2));
+/// assert_eq!(round_cast::<f64, i32>(2.5, Rounding::NearestAway), Ok(3));
+/// assert_eq!(round_cast::<f32, u8>(-0.5, Rounding::Floor).unwrap_err().kind(),
+///            CastErrorKind::Underflow);
+/// ```
+#[inline(always)]
+pub fn round_cast<V, T: RoundCastAs<V>>(v: V, mode: Rounding) -> Result<T, CastError> {
+    RoundCastAs::try_round_from(v, mode)
+}
+
+/// Rounds `v` with `mode` and casts it into `T`, clamping to the bounds of `T`.
+#[inline(always)]
+pub fn saturating_round_cast<V, T: RoundCastAs<V>>(v: V, mode: Rounding) -> T {
+    RoundCastAs::saturating_round_from(v, mode)
+}
+
+/// Casts the integral float `v` into `T`, failing if it has a fractional part or does not fit.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(exact_cast::<f64, u64>(1e15), Ok(1000000000000000));
+/// assert_eq!(exact_cast::<f64, u64>(0.5).unwrap_err().kind(), CastErrorKind::Fractional);
+/// ```
+#[inline(always)]
+pub fn exact_cast<V, T: RoundCastAs<V>>(v: V) -> Result<T, CastError> {
+    RoundCastAs::exact_from(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all(f: f64) -> [i64; 5] {
+        let mut out = [0; 5];
+        let modes = [Rounding::Floor, Rounding::Ceil, Rounding::NearestEven,
+                     Rounding::NearestAway, Rounding::TowardZero];
+        for (o, &m) in out.iter_mut().zip(modes.iter()) {
+            *o = round_cast(f, m).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(all(2.5), [2, 3, 2, 3, 2]);
+        assert_eq!(all(3.5), [3, 4, 4, 4, 3]);
+        assert_eq!(all(-2.5), [-3, -2, -2, -3, -2]);
+        assert_eq!(all(-2.7), [-3, -2, -3, -3, -2]);
+        assert_eq!(all(0.2), [0, 1, 0, 0, 0]);
+        assert_eq!(all(7.0), [7, 7, 7, 7, 7]);
+        assert_eq!(all(4503599627370497.0), [4503599627370497; 5]);
+        assert_eq!(round_cast::<f32, i32>(-8388607.5, Rounding::NearestEven), Ok(-8388608));
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(round_cast::<f64, u8>(255.4, Rounding::NearestEven), Ok(255));
+        assert_eq!(round_cast::<f64, u8>(255.5, Rounding::NearestEven).unwrap_err().kind(),
+                   CastErrorKind::Overflow);
+        assert_eq!(round_cast::<f32, u32>(f32::NAN, Rounding::Floor).unwrap_err().kind(),
+                   CastErrorKind::NaN);
+        assert_eq!(round_cast::<f32, u128>(3e38, Rounding::Floor), Ok(3e38f32 as u128));
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(saturating_round_cast::<f64, u8>(255.5, Rounding::Ceil), 255);
+        assert_eq!(saturating_round_cast::<f64, i8>(-0.5, Rounding::Floor), -1);
+        assert_eq!(saturating_round_cast::<f32, u16>(-3.0, Rounding::Ceil), 0);
+        assert_eq!(saturating_round_cast::<f32, u16>(f32::NAN, Rounding::Ceil), 0);
+    }
+
+    #[test]
+    fn exact() {
+        assert_eq!(exact_cast::<f32, i16>(-12.0), Ok(-12));
+        assert_eq!(exact_cast::<f32, i16>(-12.5).unwrap_err().kind(), CastErrorKind::Fractional);
+        assert_eq!(exact_cast::<f64, i16>(40000.0).unwrap_err().kind(), CastErrorKind::Overflow);
+    }
+}
@@ -0,0 +1,102 @@
+/// `Self` can be built from any `T`, clamping values which do not fit to `Self`'s bounds.
+///
+/// Integers clamp to `MIN`/`MAX`. Floats going into integers clamp the same way, and NaN becomes
+/// zero, like `as` does. Floats going into a narrower float clamp finite values to `MIN`/`MAX`
+/// and keep infinities and NaN as they are.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be saturating cast from `{T}`",
+    label = "only the primitive numbers can be saturating cast"
+)]
+pub trait SaturatingCastAs<T>: Sized {
+    fn saturating_from(t: T) -> Self;
+}
+
+macro_rules! saturating_rule {
+    // Walk the sources, pairing each with every target.
+    (@rows [$($to:tt)*]) => ();
+    (@rows [$($to:tt)*] ($kb:ident $b:ident) $($rest:tt)*) => (
+        $(saturating_rule!(@pair $kb $b $to);)*
+        saturating_rule!(@rows [$($to)*] $($rest)*);
+    );
+    (@pair $kb:ident $b:ident ($ka:ident $a:ident)) => (
+        impl SaturatingCastAs<$b> for $a {
+            #[inline]
+            fn saturating_from(t: $b) -> $a { saturating_rule!(@clamp $kb $b $ka $a t) }
+        }
+    );
+    (@clamp float $b:ident float $a:ident $t:ident) => ({
+        let r = $t as $a;
+        if r.is_infinite() && $t.is_finite() {
+            if $t > 0.0 { <$a>::MAX } else { <$a>::MIN }
+        } else {
+            r
+        }
+    });
+    // Only `u128` to `f32` can overflow, and it only overflows upwards.
+    (@clamp $kb:ident $b:ident float $a:ident $t:ident) => ({
+        let r = $t as $a;
+        if r.is_infinite() { <$a>::MAX } else { r }
+    });
+    (@clamp unsigned $b:ident $ka:ident $a:ident $t:ident) => (
+        <$a as TryFrom<$b>>::try_from($t).unwrap_or(<$a>::MAX)
+    );
+    (@clamp signed $b:ident $ka:ident $a:ident $t:ident) => (
+        <$a as TryFrom<$b>>::try_from($t).unwrap_or(if $t < 0 { <$a>::MIN } else { <$a>::MAX })
+    );
+    // `as` already saturates and sends NaN to zero.
+    (@clamp float $b:ident $ka:ident $a:ident $t:ident) => ($t as $a);
+    ($($k:ident: $($t:ident)*;)*) => (
+        saturating_rule!(@rows [$($(($k $t))*)*] $($(($k $t))*)*);
+    );
+}
+
+saturating_rule! {
+    unsigned: u8 u16 u32 u64 u128 usize;
+    signed: i8 i16 i32 i64 i128 isize;
+    float: f32 f64;
+}
+
+/// Casts `v` into `T`, clamping it to the bounds of `T` when it does not fit.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(saturating_cast::<i32, u8>(300), 255);
+/// assert_eq!(saturating_cast::<i32, u8>(-5), 0);
+/// assert_eq!(saturating_cast::<f64, i16>(-1e9), i16::MIN);
+/// assert_eq!(saturating_cast::<f32, u32>(f32::NAN), 0);
+/// ```
+#[inline(always)]
+pub fn saturating_cast<V, T: SaturatingCastAs<V>>(v: V) -> T {
+    SaturatingCastAs::saturating_from(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(saturating_cast::<u64, u8>(1000), u8::MAX);
+        assert_eq!(saturating_cast::<i8, u64>(-1), 0);
+        assert_eq!(saturating_cast::<u32, i32>(u32::MAX), i32::MAX);
+        assert_eq!(saturating_cast::<i128, i8>(i128::MIN), i8::MIN);
+        assert_eq!(saturating_cast::<i16, i16>(-7), -7);
+        assert_eq!(saturating_cast::<u8, i128>(200), 200);
+        assert_eq!(saturating_cast::<u128, usize>(u128::MAX), usize::MAX);
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(saturating_cast::<f32, u8>(300.7), 255);
+        assert_eq!(saturating_cast::<f64, u8>(-0.5), 0);
+        assert_eq!(saturating_cast::<f64, i64>(f64::INFINITY), i64::MAX);
+        assert_eq!(saturating_cast::<f64, i64>(f64::NAN), 0);
+        assert_eq!(saturating_cast::<u128, f32>(u128::MAX), f32::MAX);
+        assert_eq!(saturating_cast::<i128, f32>(i128::MIN), i128::MIN as f32);
+        assert_eq!(saturating_cast::<f64, f32>(1e300), f32::MAX);
+        assert_eq!(saturating_cast::<f64, f32>(-1e300), f32::MIN);
+        assert_eq!(saturating_cast::<f64, f32>(f64::NEG_INFINITY), f32::NEG_INFINITY);
+        assert!(saturating_cast::<f64, f32>(f64::NAN).is_nan());
+        assert_eq!(saturating_cast::<f32, f64>(1.5), 1.5);
+    }
+}
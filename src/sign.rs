@@ -0,0 +1,103 @@
+use crate::{Integer, Signed, Unsigned};
+
+/// Maps an integer to the unsigned integer of the same width. For unsigned types that is `Self`.
+pub trait ToUnsigned: Integer {
+    type Unsigned: Unsigned;
+
+    /// The magnitude, which always fits: `(-128i8).unsigned_abs()` is `128u8`.
+    fn unsigned_abs(self) -> Self::Unsigned;
+
+    /// The same bits, read as unsigned, like `as` does.
+    fn reinterpret_unsigned(self) -> Self::Unsigned;
+
+    /// The distance between `self` and `other`, which always fits.
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+}
+
+/// Maps an integer to the signed integer of the same width. For signed types that is `Self`.
+pub trait ToSigned: Integer {
+    type Signed: Signed;
+
+    /// The same bits, read as signed, like `as` does.
+    fn reinterpret_signed(self) -> Self::Signed;
+
+    /// The same value, if it fits.
+    fn checked_to_signed(self) -> Option<Self::Signed>;
+}
+
+macro_rules! sign_rule {
+    ($($u:ident $s:ident;)*) => ($(
+        impl ToUnsigned for $s {
+            type Unsigned = $u;
+            #[inline(always)]
+            fn unsigned_abs(self) -> $u { <$s>::unsigned_abs(self) }
+            #[inline(always)]
+            fn reinterpret_unsigned(self) -> $u { self as $u }
+            #[inline(always)]
+            fn abs_diff(self, other: $s) -> $u { <$s>::abs_diff(self, other) }
+        }
+        impl ToUnsigned for $u {
+            type Unsigned = $u;
+            #[inline(always)]
+            fn unsigned_abs(self) -> $u { self }
+            #[inline(always)]
+            fn reinterpret_unsigned(self) -> $u { self }
+            #[inline(always)]
+            fn abs_diff(self, other: $u) -> $u { <$u>::abs_diff(self, other) }
+        }
+        impl ToSigned for $u {
+            type Signed = $s;
+            #[inline(always)]
+            fn reinterpret_signed(self) -> $s { self as $s }
+            #[inline(always)]
+            fn checked_to_signed(self) -> Option<$s> {
+                if self <= <$s>::MAX as $u { Some(self as $s) } else { None }
+            }
+        }
+        impl ToSigned for $s {
+            type Signed = $s;
+            #[inline(always)]
+            fn reinterpret_signed(self) -> $s { self }
+            #[inline(always)]
+            fn checked_to_signed(self) -> Option<$s> { Some(self) }
+        }
+    )*)
+}
+
+sign_rule! {
+    u8 i8; u16 i16; u32 i32; u64 i64; u128 i128; usize isize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance<T: ToUnsigned>(a: T, b: T) -> T::Unsigned {
+        a.abs_diff(b)
+    }
+
+    fn magnitude<T: ToUnsigned>(a: T) -> T::Unsigned {
+        a.unsigned_abs()
+    }
+
+    fn signed<T: ToSigned>(a: T) -> Option<T::Signed> {
+        a.checked_to_signed()
+    }
+
+    #[test]
+    fn to_unsigned() {
+        assert_eq!(distance(-128i8, 127), 255u8);
+        assert_eq!(distance(3u64, 10), 7);
+        assert_eq!(magnitude(i128::MIN), 1u128 << 127);
+        assert_eq!(magnitude(5usize), 5);
+        assert_eq!(ToUnsigned::reinterpret_unsigned(-1i16), u16::MAX);
+    }
+
+    #[test]
+    fn to_signed() {
+        assert_eq!(signed(127u8), Some(127i8));
+        assert_eq!(signed(128u8), None);
+        assert_eq!(signed(-3isize), Some(-3));
+        assert_eq!(ToSigned::reinterpret_signed(u32::MAX), -1i32);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Num, UpCastFrom};
+
+/// `Self` has a type of twice its width, which every value up casts into.
+pub trait Widen: Num {
+    type Wide: Num + UpCastFrom<Self>;
+
+    #[inline(always)]
+    fn widen(self) -> Self::Wide {
+        UpCastFrom::from(self)
+    }
+}
+
+/// The inverse of `Widen` for integers: `Self` is made of two halves of the narrow type.
+///
+/// For signed types the high half carries the sign, and the low half holds the low bits as they
+/// are, so `-1i16` splits into `(-1, -1)`.
+pub trait Narrow: Num {
+    type Narrow: Widen<Wide = Self>;
+
+    /// Gives the `(high, low)` halves.
+    fn split(self) -> (Self::Narrow, Self::Narrow);
+
+    fn join(hi: Self::Narrow, lo: Self::Narrow) -> Self;
+}
+
+/// The full product of `a` and `b`, which always fits in the wide type. For `f32` it is exact.
+#[inline(always)]
+pub fn widening_mul<T: Widen>(a: T, b: T) -> T::Wide {
+    a.widen() * b.widen()
+}
+
+/// Adds `a`, `b` and `carry`, and gives the low half of the sum along with the high half, which
+/// is the carry into the next digit.
+///
+/// ```
+/// # use numtraits::*;
+/// assert_eq!(carrying_add(200u8, 100, 1), (45, 1));
+/// ```
+#[inline(always)]
+pub fn carrying_add<T: Widen>(a: T, b: T, carry: T) -> (T, T)
+where
+    T::Wide: Narrow<Narrow = T>,
+{
+    let (hi, lo) = (a.widen() + b.widen() + carry.widen()).split();
+    (lo, hi)
+}
+
+macro_rules! widen {
+    ($($n:ident => $w:ident;)*) => ($(
+        impl Widen for $n {
+            type Wide = $w;
+        }
+    )*)
+}
+
+macro_rules! narrow {
+    ($($w:ident => $n:ident;)*) => ($(
+        widen!($n => $w;);
+
+        impl Narrow for $w {
+            type Narrow = $n;
+
+            #[inline(always)]
+            fn split(self) -> ($n, $n) {
+                ((self >> <$n>::BITS) as $n, self as $n)
+            }
+
+            #[inline(always)]
+            fn join(hi: $n, lo: $n) -> $w {
+                (hi as $w) << <$n>::BITS | (lo as $w & !(!0 << <$n>::BITS))
+            }
+        }
+    )*)
+}
+
+narrow! {
+    u16 => u8; u32 => u16; u64 => u32; u128 => u64;
+    i16 => i8; i32 => i16; i64 => i32; i128 => i64;
+}
+
+widen!(f32 => f64;);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square<T: Widen>(x: T) -> T::Wide {
+        widening_mul(x, x)
+    }
+
+    #[test]
+    fn widening() {
+        assert_eq!(255u8.widen(), 255u16);
+        assert_eq!(square(u64::MAX), u64::MAX as u128 * u64::MAX as u128);
+        assert_eq!(square(i64::MIN), 1i128 << 126);
+        assert_eq!(square(16777215f32), 281474943156225f64);
+    }
+
+    #[test]
+    fn split_join() {
+        assert_eq!(0x1234u16.split(), (0x12, 0x34));
+        assert_eq!((-1i16).split(), (-1, -1));
+        assert_eq!((-256i16).split(), (-1, 0));
+        assert_eq!(u128::join(1, 2), (1 << 64) + 2);
+        for x in [i32::MIN, -70000, -1, 0, 1, 65535, i32::MAX] {
+            let (hi, lo) = x.split();
+            assert_eq!(i32::join(hi, lo), x);
+        }
+    }
+
+    #[test]
+    fn carries() {
+        // 0xffff_ffff + 1, one u16 digit at a time.
+        let (lo, c) = carrying_add(0xffffu16, 1, 0);
+        let (hi, c) = carrying_add(0xffffu16, 0, c);
+        assert_eq!((hi, lo, c), (0, 0, 1));
+        assert_eq!(carrying_add(u64::MAX, u64::MAX, u64::MAX), (u64::MAX - 2, 2));
+        assert_eq!(carrying_add(-128i8, -128, 0), (0, -1));
+    }
+}
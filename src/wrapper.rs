@@ -0,0 +1,69 @@
+use core::num::{Saturating, Wrapping};
+
+use crate::{CastError, DownCastAs, Lattice, LosslessUpCastFrom, UpCastFrom};
+
+// `Wrapping<T>` and `Saturating<T>` sit in the hierarchy the way `T` does, one level up: every
+// edge between inner types is an edge between the wrapped ones.
+macro_rules! lift {
+    ($($w:ident)*) => ($(
+        lift!(@slots $w [
+            L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+        ]);
+
+        impl<V, T: UpCastFrom<V>> UpCastFrom<$w<V>> for $w<T> {
+            #[inline(always)]
+            fn from(v: $w<V>) -> $w<T> { $w(T::from(v.0)) }
+        }
+
+        impl<V, T: LosslessUpCastFrom<V>> LosslessUpCastFrom<$w<V>> for $w<T> {
+            #[inline(always)]
+            fn from_lossless(v: $w<V>) -> $w<T> { $w(T::from_lossless(v.0)) }
+        }
+
+        impl<V, T: DownCastAs<V>> DownCastAs<$w<V>> for $w<T> {
+            #[inline(always)]
+            fn try_down_from(v: $w<V>) -> Result<$w<T>, CastError> {
+                T::try_down_from(v.0).map($w)
+            }
+        }
+    )*);
+    (@slots $w:ident [$($slot:ident)*]) => (
+        impl<R, T: Lattice<R>> Lattice<R> for $w<T> {
+            $(type $slot = $w<T::$slot>;)*
+        }
+    );
+}
+
+lift!(Wrapping Saturating);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cast, cast_lossless, try_cast, CastErrorKind, FitsIn, UpCastAs};
+
+    fn count<T: UpCastAs<Wrapping<u16>>>(x: Wrapping<u8>) -> T {
+        cast(x)
+    }
+
+    fn widen<T: FitsIn<Saturating<i32>>>(t: T) -> Saturating<f64> {
+        t.up_into()
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(count::<Wrapping<u32>>(Wrapping(200)), Wrapping(200u32));
+        let x: Wrapping<i32> = count(Wrapping(200));
+        assert_eq!(x + Wrapping(i32::MAX), Wrapping(i32::MIN + 199));
+        assert_eq!(cast_lossless::<_, Wrapping<f32>>(Wrapping(3i16)), Wrapping(3.0));
+        let e = try_cast::<_, Wrapping<u8>>(Wrapping(300u64)).unwrap_err();
+        assert_eq!(e.kind(), CastErrorKind::Overflow);
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(widen(Saturating(-3i8)), Saturating(-3.0));
+        let x: Saturating<i16> = cast(Saturating(200u8));
+        assert_eq!(x + Saturating(i16::MAX), Saturating(i16::MAX));
+    }
+}